@@ -0,0 +1,153 @@
+//! Measures the cost of recording key events and querying key state many times per step, end to end through `WinitInputHelper`.
+//! Run with: `cargo bench --bench key_queries`
+//!
+//! winit does not allow constructing `KeyEvent`s outside of winit, so window key events are recorded via the hidden
+//! `WinitInputHelper::process_physical_key()`, which goes through the same code as `process_window_event()`.
+//! Raw key events go through `process_device_event()` as usual.
+use std::hint::black_box;
+use std::time::Duration;
+use web_time::Instant;
+use winit::event::{DeviceEvent, DeviceId, ElementState, RawKeyEvent};
+use winit::keyboard::{KeyCode, PhysicalKey};
+use winit_input_helper::WinitInputHelper;
+
+const STEPS: u32 = 100_000;
+
+const KEYS: [KeyCode; 48] = [
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::Space,
+    KeyCode::Enter,
+    KeyCode::Escape,
+    KeyCode::Tab,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+];
+
+fn window_key(input: &mut WinitInputHelper, keycode: KeyCode, state: ElementState) {
+    input.process_physical_key(DeviceId::dummy(), PhysicalKey::Code(keycode), state);
+}
+
+fn raw_key(input: &mut WinitInputHelper, keycode: KeyCode, state: ElementState) {
+    let event = DeviceEvent::Key(RawKeyEvent {
+        physical_key: PhysicalKey::Code(keycode),
+        state,
+    });
+    input.process_device_event(DeviceId::dummy(), &event);
+}
+
+fn bench_window_keys() {
+    let mut input = WinitInputHelper::new();
+
+    // Hold half of the keys for the entire benchmark
+    for keycode in KEYS.iter().step_by(2) {
+        window_key(&mut input, *keycode, ElementState::Pressed);
+    }
+
+    let start = Instant::now();
+    for i in 0..STEPS {
+        input.step();
+
+        // Tap one of the keys that are not held and repeat one of the held keys every step
+        let tapped = KEYS[(i as usize * 2 + 1) % KEYS.len()];
+        let repeated = KEYS[(i as usize * 2) % KEYS.len()];
+        window_key(&mut input, tapped, ElementState::Pressed);
+        window_key(&mut input, repeated, ElementState::Pressed);
+        window_key(&mut input, tapped, ElementState::Released);
+
+        input.end_step();
+
+        let mut count = 0;
+        for keycode in KEYS {
+            count += input.key_pressed(keycode) as u32;
+            count += input.key_pressed_os(keycode) as u32;
+            count += input.key_released(keycode) as u32;
+            count += input.key_held(keycode) as u32;
+        }
+        black_box(count);
+    }
+    report("window keys", KEYS.len() * 4, start.elapsed());
+}
+
+fn bench_raw_keys() {
+    let mut input = WinitInputHelper::new();
+
+    // Hold half of the keys for the entire benchmark
+    for keycode in KEYS.iter().step_by(2) {
+        raw_key(&mut input, *keycode, ElementState::Pressed);
+    }
+
+    let start = Instant::now();
+    for i in 0..STEPS {
+        input.step();
+
+        // Tap one of the keys that are not held every step
+        let tapped = KEYS[(i as usize * 2 + 1) % KEYS.len()];
+        raw_key(&mut input, tapped, ElementState::Pressed);
+        raw_key(&mut input, tapped, ElementState::Released);
+
+        input.end_step();
+
+        let mut count = 0;
+        for keycode in KEYS {
+            count += input.raw_key_pressed(keycode) as u32;
+            count += input.raw_key_held(keycode) as u32;
+            count += input.raw_key_released(keycode) as u32;
+        }
+        black_box(count);
+    }
+    report("raw keys", KEYS.len() * 3, start.elapsed());
+}
+
+fn report(name: &str, queries: usize, elapsed: Duration) {
+    println!(
+        "{name}: {STEPS} steps of {queries} key queries, {:?} total, {:?} per step",
+        elapsed,
+        elapsed / STEPS
+    );
+}
+
+fn main() {
+    bench_window_keys();
+    bench_raw_keys();
+}
@@ -15,6 +15,8 @@ struct App {
 
 impl ApplicationHandler for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        self.input.process_resumed();
+
         // This method is called when the app is resumed, including when it
         //  is first started. If we do not have a window, we have to create one.
         if self.window.is_none() {
@@ -32,8 +34,16 @@ impl ApplicationHandler for App {
         }
     }
 
-    fn device_event(&mut self, _: &ActiveEventLoop, _: DeviceId, event: DeviceEvent) {
-        self.input.process_device_event(&event);
+    fn suspended(&mut self, _: &ActiveEventLoop) {
+        self.input.process_suspended();
+    }
+
+    fn memory_warning(&mut self, _: &ActiveEventLoop) {
+        self.input.process_memory_warning();
+    }
+
+    fn device_event(&mut self, _: &ActiveEventLoop, device_id: DeviceId, event: DeviceEvent) {
+        self.input.process_device_event(device_id, &event);
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
@@ -65,6 +75,14 @@ impl ApplicationHandler for App {
             log::info!("The 'R' key (US layout) is held");
         }
 
+        if self.input.raw_key_pressed(KeyCode::KeyT) {
+            log::info!("The 'T' key (US layout) was pressed on the keyboard, even if the window is unfocused");
+        }
+
+        for key in self.input.keys_pressed() {
+            log::info!("The {:?} key was pressed", key);
+        }
+
         // Logical keys are usually used for text input and rarely make sense in the way they are presented in this API.
 
         if self.input.key_pressed_logical(Key::Character("a")) {
@@ -101,6 +119,10 @@ impl ApplicationHandler for App {
             log::info!("The scroll diff is: {:?}", scroll_diff);
         }
 
+        if let Some(phase) = self.input.scroll_phase() {
+            log::info!("The scroll phase is: {:?}", phase);
+        }
+
         for button in [MouseButton::Left, MouseButton::Right, MouseButton::Middle] {
             if self.input.mouse_pressed(button) {
                 log::info!("The {:?} mouse button was pressed", button);
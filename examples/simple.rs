@@ -23,8 +23,8 @@ impl ApplicationHandler for App {
         }
     }
 
-    fn device_event(&mut self, _: &ActiveEventLoop, _: DeviceId, event: DeviceEvent) {
-        self.input.process_device_event(&event);
+    fn device_event(&mut self, _: &ActiveEventLoop, device_id: DeviceId, event: DeviceEvent) {
+        self.input.process_device_event(device_id, &event);
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
@@ -0,0 +1,85 @@
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+/// Tracks which inputs have been consumed during the current step and by which input layer.
+///
+/// An input consumed by a layer is hidden from queries made from that layer or any lower layer.
+#[derive(Clone)]
+pub struct Consumption {
+    pub keys: Vec<(KeyCode, u32)>,
+    pub mouse: Vec<(MouseButton, u32)>,
+    pub scroll: Option<u32>,
+    pub all_keyboard: Option<u32>,
+}
+
+impl Consumption {
+    pub fn new() -> Consumption {
+        Consumption {
+            keys: vec![],
+            mouse: vec![],
+            scroll: None,
+            all_keyboard: None,
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.keys.clear();
+        self.mouse.clear();
+        self.scroll = None;
+        self.all_keyboard = None;
+    }
+
+    pub fn consume_key(&mut self, keycode: KeyCode, layer: u32) {
+        match self.keys.iter_mut().find(|(k, _)| *k == keycode) {
+            Some((_, consumed_by)) => *consumed_by = (*consumed_by).max(layer),
+            None => self.keys.push((keycode, layer)),
+        }
+    }
+
+    pub fn consume_mouse(&mut self, button: MouseButton, layer: u32) {
+        match self.mouse.iter_mut().find(|(b, _)| *b == button) {
+            Some((_, consumed_by)) => *consumed_by = (*consumed_by).max(layer),
+            None => self.mouse.push((button, layer)),
+        }
+    }
+
+    pub fn consume_scroll(&mut self, layer: u32) {
+        self.scroll = Some(
+            self.scroll
+                .map_or(layer, |consumed_by| consumed_by.max(layer)),
+        );
+    }
+
+    pub fn consume_all_keyboard(&mut self, layer: u32) {
+        self.all_keyboard = Some(
+            self.all_keyboard
+                .map_or(layer, |consumed_by| consumed_by.max(layer)),
+        );
+    }
+
+    /// Returns true if the key is hidden from queries made from `layer`.
+    pub fn key(&self, keycode: KeyCode, layer: u32) -> bool {
+        self.keyboard(layer)
+            || self
+                .keys
+                .iter()
+                .any(|(k, consumed_by)| *k == keycode && *consumed_by >= layer)
+    }
+
+    /// Returns true if the entire keyboard is hidden from queries made from `layer`.
+    pub fn keyboard(&self, layer: u32) -> bool {
+        matches!(self.all_keyboard, Some(consumed_by) if consumed_by >= layer)
+    }
+
+    /// Returns true if the mouse button is hidden from queries made from `layer`.
+    pub fn mouse(&self, button: MouseButton, layer: u32) -> bool {
+        self.mouse
+            .iter()
+            .any(|(b, consumed_by)| *b == button && *consumed_by >= layer)
+    }
+
+    /// Returns true if scrolling is hidden from queries made from `layer`.
+    pub fn scroll(&self, layer: u32) -> bool {
+        matches!(self.scroll, Some(consumed_by) if consumed_by >= layer)
+    }
+}
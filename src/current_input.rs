@@ -1,20 +1,43 @@
-use winit::event::{DeviceEvent, ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::event::{
+    DeviceEvent, ElementState, Force, MouseButton, MouseScrollDelta, Touch, TouchPhase, WindowEvent,
+};
 use winit::keyboard::{Key, PhysicalKey};
 
+use crate::key_set::{KeySet, KeyTimes};
+use std::collections::VecDeque;
+use web_time::Instant;
+
+// I just took this from three-rs, no idea why this magic number was chosen ¯\_(ツ)_/¯
+pub const PIXELS_PER_LINE: f64 = 38.0;
+
+/// The number of `CursorSample`s kept in `CurrentInput::cursor_history`.
+pub const CURSOR_HISTORY_LEN: usize = 32;
+
 #[derive(Clone)]
 pub struct CurrentInput {
-    pub mouse_actions: Vec<MouseAction>,
-    pub key_actions: Vec<KeyAction>,
-    pub scancode_actions: Vec<ScanCodeAction>,
+    pub mouse_actions: Vec<(MouseAction, Instant)>,
+    pub key_actions: Vec<(KeyAction, Instant)>,
+    pub scancode_pressed: KeySet,
+    pub scancode_pressed_at: KeyTimes,
+    pub scancode_pressed_os: KeySet,
+    pub scancode_released: KeySet,
+    pub scancode_released_at: KeyTimes,
     pub key_held: Vec<Key>,
-    pub scancode_held: Vec<PhysicalKey>, // some scan codes are higher than 255 so using an array may be dangerous
-    pub mouse_held: [bool; 255],
+    pub scancode_held: KeySet,
+    /// Only the held buttons are stored, so any `MouseButton::Other` id is supported.
+    pub mouse_held: Vec<MouseButton>,
     pub cursor_point: Option<(f32, f32)>,
     pub cursor_point_prev: Option<(f32, f32)>,
+    pub cursor_history: VecDeque<CursorSample>,
+    pub cursor_path: Vec<CursorSample>,
     pub mouse_diff: Option<(f32, f32)>,
     pub y_scroll_diff: f32,
     pub x_scroll_diff: f32,
+    pub scroll_phase: Option<TouchPhase>,
     pub text: Vec<Key>,
+    pub pen: Option<Pen>,
+    pub pen_pressed: bool,
+    pub pen_released: bool,
 }
 
 impl CurrentInput {
@@ -22,91 +45,109 @@ impl CurrentInput {
         CurrentInput {
             mouse_actions: vec![],
             key_actions: vec![],
-            scancode_actions: vec![],
+            scancode_pressed: KeySet::new(),
+            scancode_pressed_at: KeyTimes::new(),
+            scancode_pressed_os: KeySet::new(),
+            scancode_released: KeySet::new(),
+            scancode_released_at: KeyTimes::new(),
             key_held: vec![],
-            scancode_held: vec![],
-            mouse_held: [false; 255],
+            scancode_held: KeySet::new(),
+            mouse_held: vec![],
             cursor_point: None,
             cursor_point_prev: None,
+            cursor_history: VecDeque::with_capacity(CURSOR_HISTORY_LEN),
+            cursor_path: vec![],
             mouse_diff: None,
             y_scroll_diff: 0.0,
             x_scroll_diff: 0.0,
+            scroll_phase: None,
             text: vec![],
+            pen: None,
+            pen_pressed: false,
+            pen_released: false,
         }
     }
 
     pub fn step(&mut self) {
         self.mouse_actions.clear();
         self.key_actions.clear();
-        self.scancode_actions.clear();
+        self.scancode_pressed.clear();
+        self.scancode_pressed_os.clear();
+        self.scancode_released.clear();
         self.cursor_point_prev = self.cursor_point;
+        self.cursor_path.clear();
         self.mouse_diff = None;
         self.y_scroll_diff = 0.0;
         self.x_scroll_diff = 0.0;
+        self.scroll_phase = None;
         self.text.clear();
+        self.pen_pressed = false;
+        self.pen_released = false;
     }
 
     pub fn handle_event(&mut self, event: &WindowEvent) {
+        let now = Instant::now();
         match event {
             WindowEvent::KeyboardInput { event, .. } => match event.state {
                 ElementState::Pressed => {
                     let logical_key = &event.logical_key;
                     if !self.key_held.contains(logical_key) {
                         self.key_actions
-                            .push(KeyAction::Pressed(logical_key.clone()));
+                            .push((KeyAction::Pressed(logical_key.clone()), now));
+                        self.key_held.push(logical_key.clone());
                     }
 
-                    self.key_held.push(logical_key.clone());
                     self.key_actions
-                        .push(KeyAction::PressedOs(logical_key.clone()));
+                        .push((KeyAction::PressedOs(logical_key.clone()), now));
                     self.text.push(logical_key.clone());
 
-                    let physical_key = &event.physical_key;
-                    if !self.scancode_held.contains(physical_key) {
-                        self.scancode_actions
-                            .push(ScanCodeAction::Pressed(*physical_key));
-                        self.scancode_held.push(*physical_key);
-                    }
-
-                    self.scancode_actions
-                        .push(ScanCodeAction::PressedOs(*physical_key));
+                    self.handle_physical_key(event.physical_key, ElementState::Pressed, now);
                 }
                 ElementState::Released => {
                     let logical_key = &event.logical_key;
                     self.key_held.retain(|x| x != logical_key);
                     self.key_actions
-                        .push(KeyAction::Released(logical_key.clone()));
+                        .push((KeyAction::Released(logical_key.clone()), now));
 
-                    let physical_key = &event.physical_key;
-                    self.scancode_held.retain(|x| x != physical_key);
-                    self.scancode_actions
-                        .push(ScanCodeAction::Released(*physical_key));
+                    self.handle_physical_key(event.physical_key, ElementState::Released, now);
                 }
             },
             WindowEvent::CursorMoved { position, .. } => {
-                self.cursor_point = Some((position.x as f32, position.y as f32));
+                let position = (position.x as f32, position.y as f32);
+                self.cursor_point = Some(position);
+
+                let sample = CursorSample {
+                    position,
+                    time: now,
+                };
+                self.cursor_path.push(sample);
+                if self.cursor_history.len() == CURSOR_HISTORY_LEN {
+                    self.cursor_history.pop_front();
+                }
+                self.cursor_history.push_back(sample);
             }
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button,
                 ..
             } => {
-                let button_usize = mouse_button_to_int(button);
-                self.mouse_held[button_usize] = true;
-                self.mouse_actions.push(MouseAction::Pressed(*button));
+                if !self.mouse_held.contains(button) {
+                    self.mouse_held.push(*button);
+                }
+                self.mouse_actions
+                    .push((MouseAction::Pressed(*button), now));
             }
             WindowEvent::MouseInput {
                 state: ElementState::Released,
                 button,
                 ..
             } => {
-                let button_usize = mouse_button_to_int(button);
-                self.mouse_held[button_usize] = false;
-                self.mouse_actions.push(MouseAction::Released(*button));
+                self.mouse_held.retain(|x| x != button);
+                self.mouse_actions
+                    .push((MouseAction::Released(*button), now));
             }
-            WindowEvent::MouseWheel { delta, .. } => {
-                // I just took this from three-rs, no idea why this magic number was chosen ¯\_(ツ)_/¯
-                const PIXELS_PER_LINE: f64 = 38.0;
+            WindowEvent::MouseWheel { delta, phase, .. } => {
+                self.scroll_phase = Some(*phase);
 
                 match delta {
                     MouseScrollDelta::LineDelta(x, y) => {
@@ -119,10 +160,53 @@ impl CurrentInput {
                     }
                 }
             }
+            WindowEvent::Touch(touch) => {
+                if let Some(pen) = Pen::from_touch(touch) {
+                    match touch.phase {
+                        TouchPhase::Started => self.pen_pressed = true,
+                        TouchPhase::Ended | TouchPhase::Cancelled => self.pen_released = true,
+                        TouchPhase::Moved => {}
+                    }
+                    self.pen = Some(pen);
+                }
+            }
             _ => {}
         }
     }
 
+    /// Updates the physical key state for a key event of a `WindowEvent::KeyboardInput`.
+    pub fn handle_physical_key(
+        &mut self,
+        physical_key: PhysicalKey,
+        state: ElementState,
+        now: Instant,
+    ) {
+        match state {
+            ElementState::Pressed => {
+                if self.scancode_held.insert(physical_key)
+                    && self.scancode_pressed.insert(physical_key)
+                {
+                    self.scancode_pressed_at.set(physical_key, now);
+                }
+                self.scancode_pressed_os.insert(physical_key);
+            }
+            ElementState::Released => {
+                self.scancode_held.remove(physical_key);
+                if self.scancode_released.insert(physical_key) {
+                    self.scancode_released_at.set(physical_key, now);
+                }
+            }
+        }
+    }
+
+    /// Returns the time of the earliest transition recorded during the step.
+    pub fn first_transition_time(&self) -> Option<Instant> {
+        let key_times = self.key_actions.iter().map(|(_, time)| *time);
+        // Every keyboard event records a logical key action, so physical keys do not need to be checked.
+        let mouse_times = self.mouse_actions.iter().map(|(_, time)| *time);
+        key_times.chain(mouse_times).min()
+    }
+
     pub fn handle_device_event(&mut self, event: &DeviceEvent) {
         if let DeviceEvent::MouseMotion { delta, .. } = event {
             match self.mouse_diff {
@@ -133,6 +217,54 @@ impl CurrentInput {
     }
 }
 
+/// A cursor position and the time the `CursorMoved` event for it was processed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CursorSample {
+    pub position: (f32, f32),
+    pub time: Instant,
+}
+
+/// The state of a pen or stylus.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pen {
+    /// Identifies the pen, as reported by `winit::event::Touch::id`
+    pub id: u64,
+    /// Coordinates in pixels.
+    pub position: (f32, f32),
+    /// The pressure applied by the pen, from `0.0` to `1.0`.
+    pub pressure: f32,
+    /// The angle between the pen and the surface in radians.
+    /// `0.0` when the pen is parallel to the surface and `PI / 2.0` when it is perpendicular.
+    pub altitude: f32,
+    /// True while the pen touches the surface.
+    /// winit does not report hovering pens, so this is only false after the pen was lifted, reporting the last known position.
+    pub contact: bool,
+}
+
+impl Pen {
+    /// Returns `None` if the touch was not made by a pen.
+    ///
+    /// winit does not tell us which device made a touch, so only touches reporting an altitude angle are considered to be made by a pen.
+    /// Currently this is only the Apple Pencil on iOS.
+    pub(crate) fn from_touch(touch: &Touch) -> Option<Pen> {
+        match touch.force {
+            Some(
+                force @ Force::Calibrated {
+                    altitude_angle: Some(altitude),
+                    ..
+                },
+            ) => Some(Pen {
+                id: touch.id,
+                position: (touch.location.x as f32, touch.location.y as f32),
+                pressure: force.normalized().clamp(0.0, 1.0) as f32,
+                altitude: altitude as f32,
+                contact: matches!(touch.phase, TouchPhase::Started | TouchPhase::Moved),
+            }),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum KeyAction {
     Pressed(Key),
@@ -141,25 +273,7 @@ pub enum KeyAction {
 }
 
 #[derive(Clone, PartialEq)]
-pub enum ScanCodeAction {
-    Pressed(PhysicalKey),
-    PressedOs(PhysicalKey),
-    Released(PhysicalKey),
-}
-
-#[derive(Clone)]
 pub enum MouseAction {
     Pressed(MouseButton),
     Released(MouseButton),
 }
-
-pub fn mouse_button_to_int(button: &MouseButton) -> usize {
-    match button {
-        MouseButton::Left => 0,
-        MouseButton::Right => 1,
-        MouseButton::Middle => 2,
-        MouseButton::Back => 3,
-        MouseButton::Forward => 4,
-        MouseButton::Other(byte) => 5 + *byte as usize,
-    }
-}
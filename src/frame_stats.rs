@@ -0,0 +1,65 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+use web_time::Instant;
+
+/// Statistics over the durations of recent steps, updated by `WinitInputHelper::end_step()`.
+#[derive(Clone)]
+pub struct FrameStats {
+    pub durations: VecDeque<Duration>,
+    pub window: usize,
+    pub created: Instant,
+    pub step_count: u64,
+}
+
+impl FrameStats {
+    pub fn new() -> FrameStats {
+        FrameStats {
+            durations: VecDeque::with_capacity(120),
+            window: 120,
+            created: Instant::now(),
+            step_count: 0,
+        }
+    }
+
+    pub fn record(&mut self, duration: Duration) {
+        self.step_count += 1;
+        while self.durations.len() >= self.window {
+            self.durations.pop_front();
+        }
+        self.durations.push_back(duration);
+    }
+
+    pub fn set_window(&mut self, window: usize) {
+        self.window = window.max(1);
+        while self.durations.len() > self.window {
+            self.durations.pop_front();
+        }
+    }
+
+    pub fn average(&self) -> Option<Duration> {
+        if self.durations.is_empty() {
+            return None;
+        }
+        let total: Duration = self.durations.iter().sum();
+        Some(total / self.durations.len() as u32)
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.durations.iter().min().copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.durations.iter().max().copied()
+    }
+
+    /// Uses the nearest rank method, `percentile` is clamped between `0.0` and `100.0`.
+    pub fn percentile(&self, percentile: f32) -> Option<Duration> {
+        if self.durations.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.durations.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * sorted.len() as f32).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1)])
+    }
+}
@@ -0,0 +1,377 @@
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks};
+use gilrs::{Axis, Button, EventType, GamepadId, Gilrs};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Gamepad input state built from gilrs events.
+#[derive(Clone)]
+pub struct GamepadInput {
+    pub gilrs: GilrsHandle,
+    pub gamepads: Vec<GamepadState>,
+    pub connected: Vec<GamepadId>,
+    pub disconnected: Vec<GamepadId>,
+    pub dead_zone: f32,
+    /// The playing rumble effects and when they end, `None` if they outlast any representable `Instant`.
+    pub effects: Vec<(GamepadId, Effect, Option<Instant>)>,
+}
+
+#[derive(Clone)]
+pub struct GamepadState {
+    pub id: GamepadId,
+    pub button_actions: Vec<GamepadButtonAction>,
+    pub button_held: Vec<Button>,
+    pub axes: Vec<(Axis, f32)>,
+    /// True if a button was pressed or an axis moved beyond the dead zone during the step.
+    pub active: bool,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum GamepadButtonAction {
+    Pressed(Button),
+    Released(Button),
+}
+
+/// Gilrs is not `Clone`, so a cloned `WinitInputHelper` keeps the gamepad state but stops polling for gamepad events.
+///
+/// Gilrs is also not `Sync`, the `Mutex` keeps `WinitInputHelper` `Sync` when the `gilrs` feature is enabled.
+/// It is only locked by methods taking `&self`, everything else goes through `Mutex::get_mut()`.
+pub struct GilrsHandle(pub Option<Mutex<Gilrs>>);
+
+impl GilrsHandle {
+    pub fn new(gilrs: Option<Gilrs>) -> GilrsHandle {
+        GilrsHandle(gilrs.map(Mutex::new))
+    }
+
+    fn get_mut(&mut self) -> Option<&mut Gilrs> {
+        self.0
+            .as_mut()
+            .map(|gilrs| gilrs.get_mut().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl Clone for GilrsHandle {
+    fn clone(&self) -> Self {
+        GilrsHandle(None)
+    }
+}
+
+impl GamepadInput {
+    pub fn new() -> GamepadInput {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            // Use the dummy backend so gamepad methods keep working on unsupported platforms
+            Err(gilrs::Error::NotImplemented(gilrs)) => Some(gilrs),
+            Err(_) => None,
+        };
+        GamepadInput::with_gilrs(gilrs)
+    }
+
+    pub fn with_gilrs(gilrs: Option<Gilrs>) -> GamepadInput {
+        let gamepads = gilrs
+            .iter()
+            .flat_map(|gilrs| gilrs.gamepads())
+            .map(|(id, _)| GamepadState::new(id))
+            .collect();
+        GamepadInput {
+            gilrs: GilrsHandle::new(gilrs),
+            gamepads,
+            connected: vec![],
+            disconnected: vec![],
+            dead_zone: 0.1,
+            effects: vec![],
+        }
+    }
+
+    /// Clears the state of the previous step and processes all pending gilrs events.
+    pub fn step(&mut self) {
+        self.connected.clear();
+        self.disconnected.clear();
+        for gamepad in &mut self.gamepads {
+            gamepad.button_actions.clear();
+            gamepad.active = false;
+        }
+        // Dropping an effect stops it, so keep them around until they are finished.
+        let now = Instant::now();
+        self.effects.retain(|(_, _, end)| rumble_playing(*end, now));
+
+        let Some(gilrs) = self.gilrs.get_mut() else {
+            return;
+        };
+        while let Some(event) = gilrs.next_event() {
+            let id = event.id;
+            match event.event {
+                EventType::Connected => {
+                    if !self.gamepads.iter().any(|gamepad| gamepad.id == id) {
+                        self.gamepads.push(GamepadState::new(id));
+                    }
+                    self.connected.push(id);
+                }
+                EventType::Disconnected => {
+                    self.gamepads.retain(|gamepad| gamepad.id != id);
+                    self.effects.retain(|(gamepad, _, _)| *gamepad != id);
+                    self.disconnected.push(id);
+                }
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(gamepad) = self.gamepads.iter_mut().find(|g| g.id == id) {
+                        if !gamepad.button_held.contains(&button) {
+                            gamepad
+                                .button_actions
+                                .push(GamepadButtonAction::Pressed(button));
+                            gamepad.button_held.push(button);
+                            gamepad.active = true;
+                        }
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(gamepad) = self.gamepads.iter_mut().find(|g| g.id == id) {
+                        gamepad.button_held.retain(|x| *x != button);
+                        gamepad
+                            .button_actions
+                            .push(GamepadButtonAction::Released(button));
+                    }
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    if let Some(gamepad) = self.gamepads.iter_mut().find(|g| g.id == id) {
+                        if value.abs() > self.dead_zone {
+                            gamepad.active = true;
+                        }
+                        match gamepad.axes.iter_mut().find(|(a, _)| *a == axis) {
+                            Some((_, current)) => *current = value,
+                            None => gamepad.axes.push((axis, value)),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Starts a rumble effect on the gamepad, replacing any rumble effect that is still playing on it.
+    pub fn rumble(&mut self, id: GamepadId, rumble: Rumble) -> Result<(), gilrs::ff::Error> {
+        let gilrs = self
+            .gilrs
+            .get_mut()
+            .ok_or(gilrs::ff::Error::Disconnected(id))?;
+
+        let play_for = Ticks::from_ms(rumble.duration.as_millis().min(u32::MAX as u128) as u32);
+        let scheduling = Replay {
+            play_for,
+            ..Default::default()
+        };
+        let effect = EffectBuilder::new()
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong {
+                    magnitude: magnitude(rumble.strong),
+                },
+                scheduling,
+                ..Default::default()
+            })
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Weak {
+                    magnitude: magnitude(rumble.weak),
+                },
+                scheduling,
+                ..Default::default()
+            })
+            .gamepads(&[id])
+            .repeat(Repeat::For(play_for))
+            .finish(gilrs)?;
+        effect.play()?;
+
+        self.stop_rumble(id);
+        self.effects
+            .push((id, effect, rumble_end(Instant::now(), rumble.duration)));
+        Ok(())
+    }
+
+    pub fn stop_rumble(&mut self, id: GamepadId) {
+        self.effects.retain(|(gamepad, _, _)| *gamepad != id);
+    }
+
+    pub fn supports_rumble(&self, id: GamepadId) -> bool {
+        match &self.gilrs.0 {
+            Some(gilrs) => gilrs
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .connected_gamepad(id)
+                .map(|gamepad| gamepad.is_ff_supported())
+                .unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn gamepad(&self, id: GamepadId) -> Option<&GamepadState> {
+        self.gamepads.iter().find(|gamepad| gamepad.id == id)
+    }
+
+    /// Returns the value of the axis with the dead zone applied to it individually.
+    pub fn axis(&self, id: GamepadId, axis: Axis) -> f32 {
+        apply_dead_zone(self.raw_axis(id, axis), self.dead_zone)
+    }
+
+    /// Returns the values of the two axes with a radial dead zone applied to them, as is appropriate for analog sticks.
+    pub fn stick(&self, id: GamepadId, x_axis: Axis, y_axis: Axis) -> (f32, f32) {
+        apply_radial_dead_zone(
+            self.raw_axis(id, x_axis),
+            self.raw_axis(id, y_axis),
+            self.dead_zone,
+        )
+    }
+
+    fn raw_axis(&self, id: GamepadId, axis: Axis) -> f32 {
+        self.gamepad(id)
+            .and_then(|gamepad| gamepad.axes.iter().find(|(a, _)| *a == axis))
+            .map(|(_, value)| *value)
+            .unwrap_or(0.0)
+    }
+}
+
+impl GamepadState {
+    fn new(id: GamepadId) -> GamepadState {
+        GamepadState {
+            id,
+            button_actions: vec![],
+            button_held: vec![],
+            axes: vec![],
+            active: false,
+        }
+    }
+}
+
+/// A rumble effect for `WinitInputHelper::gamepad_rumble()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rumble {
+    /// How long the effect plays for, pass `Duration::MAX` to rumble until stopped.
+    pub duration: Duration,
+    /// Magnitude of the strong (low frequency) motor, from `0.0` to `1.0`.
+    pub strong: f32,
+    /// Magnitude of the weak (high frequency) motor, from `0.0` to `1.0`.
+    pub weak: f32,
+}
+
+/// Values within the dead zone become `0.0`, values beyond it are rescaled to still cover the full range.
+fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value.abs() <= dead_zone {
+        0.0
+    } else {
+        value.signum() * (value.abs() - dead_zone) / (1.0 - dead_zone)
+    }
+}
+
+/// Like `apply_dead_zone()` but applied to the length of the vector, so the direction of the stick is kept.
+fn apply_radial_dead_zone(x: f32, y: f32, dead_zone: f32) -> (f32, f32) {
+    let magnitude = (x * x + y * y).sqrt();
+    if magnitude <= dead_zone {
+        return (0.0, 0.0);
+    }
+    let scale = ((magnitude - dead_zone) / (1.0 - dead_zone)).min(1.0) / magnitude;
+    (x * scale, y * scale)
+}
+
+/// Returns when a rumble started at `now` ends, or `None` if the end is too far away to represent.
+fn rumble_end(now: Instant, duration: Duration) -> Option<Instant> {
+    now.checked_add(duration)
+}
+
+fn rumble_playing(end: Option<Instant>, now: Instant) -> bool {
+    match end {
+        Some(end) => end > now,
+        None => true,
+    }
+}
+
+fn magnitude(value: f32) -> u16 {
+    (value.clamp(0.0, 1.0) * u16::MAX as f32) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected} but got {actual}"
+        );
+    }
+
+    #[test]
+    fn endless_rumble_never_expires() {
+        let now = Instant::now();
+        let end = rumble_end(now, Duration::MAX);
+        assert_eq!(end, None);
+        assert!(rumble_playing(
+            end,
+            now + Duration::from_secs(60 * 60 * 24 * 365)
+        ));
+    }
+
+    #[test]
+    fn rumble_expires_after_its_duration() {
+        let now = Instant::now();
+        let end = rumble_end(now, Duration::from_millis(100));
+        assert_eq!(end, Some(now + Duration::from_millis(100)));
+        assert!(rumble_playing(end, now));
+        assert!(!rumble_playing(end, now + Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn dead_zone_zeroes_small_values() {
+        assert_eq!(apply_dead_zone(0.0, 0.1), 0.0);
+        assert_eq!(apply_dead_zone(0.1, 0.1), 0.0);
+        assert_eq!(apply_dead_zone(-0.05, 0.1), 0.0);
+    }
+
+    #[test]
+    fn dead_zone_rescales_to_full_range() {
+        assert_close(apply_dead_zone(1.0, 0.1), 1.0);
+        assert_close(apply_dead_zone(-1.0, 0.1), -1.0);
+        assert_close(apply_dead_zone(0.55, 0.1), 0.5);
+        assert_close(apply_dead_zone(-0.55, 0.1), -0.5);
+    }
+
+    #[test]
+    fn no_dead_zone_keeps_values() {
+        assert_close(apply_dead_zone(0.3, 0.0), 0.3);
+        assert_eq!(apply_radial_dead_zone(0.3, -0.4, 0.0), (0.3, -0.4));
+    }
+
+    #[test]
+    fn radial_dead_zone_zeroes_small_vectors() {
+        // Each axis is beyond the dead zone on its own but the length is not.
+        assert_eq!(apply_radial_dead_zone(0.2, 0.2, 0.3), (0.0, 0.0));
+        assert_eq!(apply_radial_dead_zone(0.0, 0.0, 0.1), (0.0, 0.0));
+    }
+
+    #[test]
+    fn radial_dead_zone_keeps_direction() {
+        // A length of 0.5 is a third of the way from the dead zone to the edge.
+        let (x, y) = apply_radial_dead_zone(0.3, 0.4, 0.25);
+        assert_close((x * x + y * y).sqrt(), 1.0 / 3.0);
+        assert_close(x / y, 0.75);
+    }
+
+    #[test]
+    fn radial_dead_zone_clamps_to_unit_length() {
+        // Diagonals of square gates report a length greater than 1.0.
+        let (x, y) = apply_radial_dead_zone(1.0, 1.0, 0.1);
+        assert_close((x * x + y * y).sqrt(), 1.0);
+        assert_close(x, y);
+    }
+
+    #[test]
+    fn gamepad_input_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<GamepadInput>();
+        assert_send_sync::<crate::WinitInputHelper>();
+    }
+
+    #[test]
+    fn step_without_gilrs_has_no_gamepads() {
+        let mut input = GamepadInput::with_gilrs(None);
+        input.step();
+        assert!(input.gamepads.is_empty());
+        assert!(input.connected.is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+/// An input that can be bound to an action in an `InputContext`.
+///
+/// `GamepadButton` only exists with the `gilrs` feature, so matches must include a wildcard arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// The button on any connected gamepad.
+    #[cfg(feature = "gilrs")]
+    GamepadButton(gilrs::Button),
+}
+
+impl Binding {
+    /// Returns the binding that the window event started pressing, ignoring OS key repeats.
+    pub(crate) fn pressed_by(event: &WindowEvent) -> Option<Binding> {
+        match event {
+            WindowEvent::KeyboardInput { event, .. }
+                if event.state == ElementState::Pressed && !event.repeat =>
+            {
+                match event.physical_key {
+                    PhysicalKey::Code(keycode) => Some(Binding::Key(keycode)),
+                    PhysicalKey::Unidentified(_) => None,
+                }
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button,
+                ..
+            } => Some(Binding::Mouse(*button)),
+            _ => None,
+        }
+    }
+}
+
+/// A named set of action bindings, e.g. for menus, gameplay or text chat.
+///
+/// Contexts are stacked on a `WinitInputHelper` with `push_input_context()`.
+/// Action queries such as `action_pressed()` look through the stack from the top down,
+/// where each context can block inputs from reaching the contexts below it.
+#[derive(Clone, Debug)]
+pub struct InputContext {
+    name: String,
+    bindings: Vec<(String, Binding)>,
+    blocked: Vec<Binding>,
+    block_all: bool,
+    passed_through: Vec<Binding>,
+}
+
+impl InputContext {
+    pub fn new(name: impl Into<String>) -> InputContext {
+        InputContext {
+            name: name.into(),
+            bindings: vec![],
+            blocked: vec![],
+            block_all: false,
+            passed_through: vec![],
+        }
+    }
+
+    /// Triggers the action when the input is used.
+    /// An action can have multiple bindings and an input can be bound to multiple actions.
+    pub fn bind(mut self, action: impl Into<String>, binding: Binding) -> InputContext {
+        self.bindings.push((action.into(), binding));
+        self
+    }
+
+    /// Stops the input from triggering actions in contexts below this one.
+    pub fn block(mut self, binding: Binding) -> InputContext {
+        self.blocked.push(binding);
+        self
+    }
+
+    /// Stops all inputs from triggering actions in contexts below this one, except for those passed to `pass_through()`.
+    pub fn block_all(mut self) -> InputContext {
+        self.block_all = true;
+        self
+    }
+
+    /// Allows the input to trigger actions in contexts below this one despite `block_all()`.
+    pub fn pass_through(mut self, binding: Binding) -> InputContext {
+        self.passed_through.push(binding);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn blocks(&self, binding: Binding) -> bool {
+        if self.block_all {
+            !self.passed_through.contains(&binding)
+        } else {
+            self.blocked.contains(&binding)
+        }
+    }
+}
+
+/// A change to the context stack, deferred until the next step.
+#[derive(Clone, Debug)]
+pub enum ContextChange {
+    Push(InputContext),
+    Pop,
+    Remove(String),
+}
+
+pub fn apply_context_change(contexts: &mut Vec<InputContext>, change: ContextChange) {
+    match change {
+        ContextChange::Push(context) => contexts.push(context),
+        ContextChange::Pop => {
+            contexts.pop();
+        }
+        ContextChange::Remove(name) => contexts.retain(|context| context.name != name),
+    }
+}
+
+/// Returns true if `f` returns true for any binding of the action that is not blocked by a higher context.
+/// `contexts` is ordered from the bottom of the stack to the top.
+pub fn any_binding(
+    contexts: &[InputContext],
+    action: &str,
+    mut f: impl FnMut(Binding) -> bool,
+) -> bool {
+    for (i, context) in contexts.iter().enumerate().rev() {
+        for (_, binding) in context.bindings.iter().filter(|(a, _)| a == action) {
+            let blocked = contexts[i + 1..].iter().any(|above| above.blocks(*binding));
+            if !blocked && f(*binding) {
+                return true;
+            }
+        }
+    }
+    false
+}
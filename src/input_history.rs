@@ -0,0 +1,218 @@
+use std::collections::VecDeque;
+
+use crate::remote_input::TickView;
+use crate::tick_input::TickInput;
+
+/// The input of the last N ticks, for rollback netcode.
+///
+/// Push the predicted input of each tick with `push()` and replace it with `rewrite()` once the real input arrives,
+/// then resimulate from the rewritten tick by querying each tick with `tick()`.
+/// Pressed and released are recovered by comparing each tick to the one before it,
+/// so rewriting a tick also corrects the edges of the tick after it.
+#[derive(Clone, Debug)]
+pub struct InputHistory {
+    ticks: VecDeque<TickInput>,
+    /// The tick before `first_tick`, kept so the edges of the oldest tick in the history stay correct.
+    before_first: TickInput,
+    first_tick: u64,
+    capacity: usize,
+}
+
+impl InputHistory {
+    /// Creates an empty history keeping the last `capacity` ticks, the first tick pushed is tick 0.
+    pub fn new(capacity: usize) -> InputHistory {
+        InputHistory {
+            ticks: VecDeque::with_capacity(capacity),
+            before_first: TickInput::new(),
+            first_tick: 0,
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Makes the first tick pushed `tick` instead of 0, e.g. when joining a game that is already running.
+    pub fn with_start_tick(mut self, tick: u64) -> InputHistory {
+        self.first_tick = tick;
+        self
+    }
+
+    /// Appends the input of the next tick and returns its tick number.
+    /// The oldest tick is dropped once the history is full.
+    pub fn push(&mut self, input: TickInput) -> u64 {
+        if self.ticks.len() == self.capacity {
+            if let Some(oldest) = self.ticks.pop_front() {
+                self.before_first = oldest;
+            }
+            self.first_tick += 1;
+        }
+        self.ticks.push_back(input);
+        self.first_tick + self.ticks.len() as u64 - 1
+    }
+
+    /// Replaces the input of the specified tick.
+    ///
+    /// Returns true if the tick is in the history and its input changed, meaning the simulation must be rewound to that tick.
+    /// Otherwise returns false, ticks that are too old or not pushed yet are ignored.
+    pub fn rewrite(&mut self, tick: u64, input: TickInput) -> bool {
+        match self.index(tick) {
+            Some(i) if self.ticks[i] != input => {
+                self.ticks[i] = input;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the input of the specified tick, or `None` if it is too old or not pushed yet.
+    pub fn get(&self, tick: u64) -> Option<&TickInput> {
+        self.index(tick).map(|i| &self.ticks[i])
+    }
+
+    /// Returns a view for querying the specified tick, or `None` if it is too old or not pushed yet.
+    pub fn tick(&self, tick: u64) -> Option<TickView<'_>> {
+        let i = self.index(tick)?;
+        let previous = match i {
+            0 => &self.before_first,
+            _ => &self.ticks[i - 1],
+        };
+        Some(TickView::new(&self.ticks[i], previous))
+    }
+
+    /// Returns the oldest tick in the history, or `None` if nothing has been pushed yet.
+    pub fn first_tick(&self) -> Option<u64> {
+        if self.ticks.is_empty() {
+            None
+        } else {
+            Some(self.first_tick)
+        }
+    }
+
+    /// Returns the most recently pushed tick, or `None` if nothing has been pushed yet.
+    pub fn latest_tick(&self) -> Option<u64> {
+        self.first_tick()
+            .map(|first| first + self.ticks.len() as u64 - 1)
+    }
+
+    fn index(&self, tick: u64) -> Option<usize> {
+        let i = tick.checked_sub(self.first_tick)?;
+        if i < self.ticks.len() as u64 {
+            Some(i as usize)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remote_input::InputQuery;
+    use winit::keyboard::KeyCode;
+
+    fn holding_w() -> TickInput {
+        let mut input = TickInput::new();
+        input.set_key_held(KeyCode::KeyW, true);
+        input
+    }
+
+    #[test]
+    fn push_numbers_ticks() {
+        let mut history = InputHistory::new(3);
+        assert_eq!(history.first_tick(), None);
+        assert_eq!(history.latest_tick(), None);
+        assert_eq!(history.push(TickInput::new()), 0);
+        assert_eq!(history.push(TickInput::new()), 1);
+        assert_eq!(history.first_tick(), Some(0));
+        assert_eq!(history.latest_tick(), Some(1));
+    }
+
+    #[test]
+    fn start_tick_offsets_tick_numbers() {
+        let mut history = InputHistory::new(3).with_start_tick(100);
+        assert_eq!(history.push(TickInput::new()), 100);
+        assert_eq!(history.push(TickInput::new()), 101);
+        assert!(history.get(99).is_none());
+        assert!(history.get(100).is_some());
+    }
+
+    #[test]
+    fn wrap_around_drops_oldest_tick() {
+        let mut history = InputHistory::new(2);
+        history.push(holding_w());
+        history.push(TickInput::new());
+        assert_eq!(history.push(TickInput::new()), 2);
+        assert_eq!(history.first_tick(), Some(1));
+        assert_eq!(history.latest_tick(), Some(2));
+        assert!(history.get(0).is_none());
+        assert!(history.tick(0).is_none());
+    }
+
+    #[test]
+    fn oldest_tick_keeps_edges_of_dropped_tick() {
+        let mut history = InputHistory::new(2);
+        history.push(holding_w());
+        history.push(TickInput::new());
+        history.push(TickInput::new());
+
+        // Tick 0 was dropped, but tick 1 still sees W being released since tick 0.
+        let oldest = history.tick(1).unwrap();
+        assert!(oldest.key_released(KeyCode::KeyW));
+        assert!(!oldest.key_held(KeyCode::KeyW));
+    }
+
+    #[test]
+    fn first_tick_is_compared_to_no_input() {
+        let mut history = InputHistory::new(2);
+        history.push(holding_w());
+        assert!(history.tick(0).unwrap().key_pressed(KeyCode::KeyW));
+    }
+
+    #[test]
+    fn rewrite_ignores_evicted_and_future_ticks() {
+        let mut history = InputHistory::new(2).with_start_tick(10);
+        for _ in 0..3 {
+            history.push(TickInput::new());
+        }
+        assert!(!history.rewrite(10, holding_w()));
+        assert!(!history.rewrite(13, holding_w()));
+        assert!(!history.rewrite(0, holding_w()));
+        assert!(!history.rewrite(u64::MAX, holding_w()));
+        assert_eq!(history.get(11), Some(&TickInput::new()));
+        assert_eq!(history.get(12), Some(&TickInput::new()));
+    }
+
+    #[test]
+    fn rewrite_reports_whether_input_changed() {
+        let mut history = InputHistory::new(4);
+        history.push(TickInput::new());
+        assert!(!history.rewrite(0, TickInput::new()));
+        assert!(history.rewrite(0, holding_w()));
+        assert!(!history.rewrite(0, holding_w()));
+        assert_eq!(history.get(0), Some(&holding_w()));
+    }
+
+    #[test]
+    fn rewrite_recomputes_edges_of_next_tick() {
+        let mut history = InputHistory::new(4);
+        for _ in 0..3 {
+            history.push(holding_w());
+        }
+        assert!(history.tick(0).unwrap().key_pressed(KeyCode::KeyW));
+        assert!(!history.tick(1).unwrap().key_pressed(KeyCode::KeyW));
+
+        // The corrected input says W was not held until tick 1.
+        assert!(history.rewrite(0, TickInput::new()));
+        let tick_0 = history.tick(0).unwrap();
+        assert!(!tick_0.key_pressed(KeyCode::KeyW));
+        assert!(!tick_0.key_held(KeyCode::KeyW));
+        let tick_1 = history.tick(1).unwrap();
+        assert!(tick_1.key_pressed(KeyCode::KeyW));
+        assert!(tick_1.key_held(KeyCode::KeyW));
+        assert!(!history.tick(2).unwrap().key_pressed(KeyCode::KeyW));
+
+        // And that W was released again at tick 2.
+        assert!(history.rewrite(2, TickInput::new()));
+        let tick_2 = history.tick(2).unwrap();
+        assert!(tick_2.key_released(KeyCode::KeyW));
+        assert!(!tick_2.key_held(KeyCode::KeyW));
+    }
+}
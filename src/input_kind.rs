@@ -0,0 +1,58 @@
+use winit::event::{ElementState, WindowEvent};
+
+use crate::current_input::{CurrentInput, Pen};
+
+#[cfg(feature = "gilrs")]
+use gilrs::GamepadId;
+
+/// The kind of device the user last gave input with, see `WinitInputHelper::last_input_kind()`.
+///
+/// Useful for switching button prompts between keyboard and gamepad glyphs.
+///
+/// `Gamepad` only exists with the `gilrs` feature, so matches must include a wildcard arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InputKind {
+    KeyboardMouse,
+    Touch,
+    #[cfg(feature = "gilrs")]
+    Gamepad(GamepadId),
+    Pen,
+}
+
+/// Returns the kind of input the window event represents, if it is meaningful enough to change the last used input kind.
+/// `current` must already include the event.
+pub fn window_event_input_kind(
+    event: &WindowEvent,
+    current: &CurrentInput,
+    motion_threshold: f32,
+) -> Option<InputKind> {
+    match event {
+        WindowEvent::KeyboardInput { event, .. } if event.state == ElementState::Pressed => {
+            Some(InputKind::KeyboardMouse)
+        }
+        WindowEvent::MouseInput {
+            state: ElementState::Pressed,
+            ..
+        }
+        | WindowEvent::MouseWheel { .. } => Some(InputKind::KeyboardMouse),
+        WindowEvent::CursorMoved { .. } => {
+            let (x, y) = match (current.cursor_point, current.cursor_point_prev) {
+                (Some(cur), Some(prev)) => (cur.0 - prev.0, cur.1 - prev.1),
+                _ => (0.0, 0.0),
+            };
+            (x.hypot(y) > motion_threshold).then_some(InputKind::KeyboardMouse)
+        }
+        WindowEvent::Touch(touch) => match Pen::from_touch(touch) {
+            Some(_) => Some(InputKind::Pen),
+            None => Some(InputKind::Touch),
+        },
+        _ => None,
+    }
+}
+
+/// Returns `Some(InputKind::KeyboardMouse)` if the mouse motion of the step is large enough to change the last used input kind.
+pub fn mouse_motion_input_kind(current: &CurrentInput, motion_threshold: f32) -> Option<InputKind> {
+    let (x, y) = current.mouse_diff?;
+    (x.hypot(y) > motion_threshold).then_some(InputKind::KeyboardMouse)
+}
@@ -0,0 +1,141 @@
+use web_time::Instant;
+use winit::keyboard::{KeyCode, NativeKeyCode, PhysicalKey};
+
+/// Every `KeyCode` discriminant in winit 0.30 fits in the bitset, it has 194 variants.
+/// `KeyCode`s added by later winit versions that do not fit fall back to a `Vec`.
+const KEY_CODE_BITS: usize = 256;
+
+/// A set of physical keys with O(1) insertion, removal and lookup of `KeyCode`s.
+///
+/// `KeyCode`s are stored in a bitset indexed by their discriminant, the rare `PhysicalKey::Unidentified` keys fall back to a `Vec`.
+/// The keys are also kept in insertion order so they can be iterated without probing every `KeyCode`,
+/// this list is only modified when the set changes and never reallocates once it has grown to the number of keys in use.
+#[derive(Clone)]
+pub struct KeySet {
+    codes: [u64; KEY_CODE_BITS / 64],
+    overflow: Vec<KeyCode>,
+    native: Vec<NativeKeyCode>,
+    ordered: Vec<PhysicalKey>,
+}
+
+impl KeySet {
+    pub fn new() -> KeySet {
+        KeySet {
+            codes: [0; KEY_CODE_BITS / 64],
+            overflow: vec![],
+            native: vec![],
+            ordered: vec![],
+        }
+    }
+
+    /// Returns true if the key was not already in the set.
+    pub fn insert(&mut self, key: PhysicalKey) -> bool {
+        let inserted = match key {
+            PhysicalKey::Code(keycode) => match key_code_index(keycode) {
+                Some(index) => {
+                    let (word, bit) = (index / 64, 1 << (index % 64));
+                    let inserted = self.codes[word] & bit == 0;
+                    self.codes[word] |= bit;
+                    inserted
+                }
+                None => insert_vec(&mut self.overflow, keycode),
+            },
+            PhysicalKey::Unidentified(native) => insert_vec(&mut self.native, native),
+        };
+        if inserted {
+            self.ordered.push(key);
+        }
+        inserted
+    }
+
+    pub fn remove(&mut self, key: PhysicalKey) {
+        let removed = match key {
+            PhysicalKey::Code(keycode) => match key_code_index(keycode) {
+                Some(index) => {
+                    let (word, bit) = (index / 64, 1 << (index % 64));
+                    let removed = self.codes[word] & bit != 0;
+                    self.codes[word] &= !bit;
+                    removed
+                }
+                None => remove_vec(&mut self.overflow, keycode),
+            },
+            PhysicalKey::Unidentified(native) => remove_vec(&mut self.native, native),
+        };
+        if removed {
+            self.ordered.retain(|x| *x != key);
+        }
+    }
+
+    pub fn contains_code(&self, keycode: KeyCode) -> bool {
+        match key_code_index(keycode) {
+            Some(index) => self.codes[index / 64] & 1 << (index % 64) != 0,
+            None => self.overflow.contains(&keycode),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        if !self.ordered.is_empty() {
+            self.codes = [0; KEY_CODE_BITS / 64];
+            self.overflow.clear();
+            self.native.clear();
+            self.ordered.clear();
+        }
+    }
+
+    /// Returns the keys in the order they were inserted.
+    pub fn iter(&self) -> impl Iterator<Item = PhysicalKey> + '_ {
+        self.ordered.iter().copied()
+    }
+}
+
+/// A time for every `KeyCode`, stored in a fixed size array so recording a time never allocates.
+///
+/// Times are never cleared, so they are only meaningful alongside a `KeySet` that says whether the key is present.
+/// `KeyCode`s that do not fit in the array have no time.
+#[derive(Clone)]
+pub struct KeyTimes {
+    times: [Option<Instant>; KEY_CODE_BITS],
+}
+
+impl KeyTimes {
+    pub fn new() -> KeyTimes {
+        KeyTimes {
+            times: [None; KEY_CODE_BITS],
+        }
+    }
+
+    pub fn set(&mut self, key: PhysicalKey, time: Instant) {
+        if let PhysicalKey::Code(keycode) = key {
+            if let Some(index) = key_code_index(keycode) {
+                self.times[index] = Some(time);
+            }
+        }
+    }
+
+    pub fn get(&self, keycode: KeyCode) -> Option<Instant> {
+        key_code_index(keycode).and_then(|index| self.times[index])
+    }
+}
+
+fn key_code_index(keycode: KeyCode) -> Option<usize> {
+    let index = keycode as usize;
+    if index < KEY_CODE_BITS {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+fn insert_vec<T: PartialEq>(vec: &mut Vec<T>, value: T) -> bool {
+    let inserted = !vec.contains(&value);
+    if inserted {
+        vec.push(value);
+    }
+    inserted
+}
+
+fn remove_vec<T: PartialEq>(vec: &mut Vec<T>, value: T) -> bool {
+    let len = vec.len();
+    vec.retain(|x| *x != value);
+    vec.len() != len
+}
@@ -1,4 +1,30 @@
+mod consumption;
 mod current_input;
+mod frame_stats;
+#[cfg(feature = "gilrs")]
+mod gamepad;
+mod input_context;
+mod input_history;
+mod input_kind;
+mod key_set;
+mod mouse_processing;
+mod raw_input;
+mod remote_input;
+mod snapshot;
+mod tick_input;
 mod winit_input_helper;
 
+pub use crate::current_input::{CursorSample, Pen};
+pub use crate::input_context::{Binding, InputContext};
+pub use crate::input_history::InputHistory;
+pub use crate::input_kind::InputKind;
+pub use crate::mouse_processing::{MouseAcceleration, MouseProcessing};
+pub use crate::remote_input::{InputQuery, RemoteInput, TickView};
+pub use crate::snapshot::{InputSnapshot, InputSnapshotField};
+pub use crate::tick_input::{DecodeError, TickInput};
 pub use crate::winit_input_helper::WinitInputHelper;
+
+#[cfg(feature = "gilrs")]
+pub use crate::gamepad::Rumble;
+#[cfg(feature = "gilrs")]
+pub use gilrs;
@@ -0,0 +1,75 @@
+/// Configures how `WinitInputHelper::mouse_diff_processed()` is computed from the raw `mouse_diff()`.
+///
+/// The steps are applied in the order of the fields: sensitivity, inversion, acceleration and then smoothing.
+#[derive(Clone, Debug)]
+pub struct MouseProcessing {
+    /// Multiplier applied to the (horizontal, vertical) mouse motion.
+    pub sensitivity: (f32, f32),
+    /// Flips the vertical mouse motion, commonly used for flight controls.
+    pub invert_y: bool,
+    pub acceleration: MouseAcceleration,
+    /// Exponentially smooths the mouse motion over roughly this many steps.
+    /// `0` and `1` disable smoothing.
+    pub smoothing_frames: u32,
+}
+
+impl Default for MouseProcessing {
+    fn default() -> Self {
+        MouseProcessing {
+            sensitivity: (1.0, 1.0),
+            invert_y: false,
+            acceleration: MouseAcceleration::None,
+            smoothing_frames: 0,
+        }
+    }
+}
+
+/// Acceleration curves map the distance the mouse moved during a step to the distance that is reported.
+/// The direction of motion is kept.
+#[derive(Clone, Debug)]
+pub enum MouseAcceleration {
+    None,
+    /// The motion is multiplied by `1.0 + factor * distance`.
+    Linear(f32),
+    /// The distance is raised to the given exponent.
+    Power(f32),
+    /// The distance is mapped by the provided function.
+    Custom(fn(f32) -> f32),
+}
+
+impl MouseAcceleration {
+    fn apply(&self, distance: f32) -> f32 {
+        match self {
+            MouseAcceleration::None => distance,
+            MouseAcceleration::Linear(factor) => distance * (1.0 + factor * distance),
+            MouseAcceleration::Power(exponent) => distance.powf(*exponent),
+            MouseAcceleration::Custom(curve) => curve(distance),
+        }
+    }
+}
+
+impl MouseProcessing {
+    /// Processes the raw mouse motion of a step, `previous` is the value this returned for the previous step.
+    pub(crate) fn process(&self, raw: (f32, f32), previous: (f32, f32)) -> (f32, f32) {
+        let mut x = raw.0 * self.sensitivity.0;
+        let mut y = raw.1 * self.sensitivity.1;
+        if self.invert_y {
+            y = -y;
+        }
+
+        let distance = (x * x + y * y).sqrt();
+        if distance > 0.0 {
+            let scale = self.acceleration.apply(distance) / distance;
+            x *= scale;
+            y *= scale;
+        }
+
+        if self.smoothing_frames > 1 {
+            let alpha = 2.0 / (self.smoothing_frames as f32 + 1.0);
+            x = alpha * x + (1.0 - alpha) * previous.0;
+            y = alpha * y + (1.0 - alpha) * previous.1;
+        }
+
+        (x, y)
+    }
+}
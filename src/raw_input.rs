@@ -0,0 +1,101 @@
+use winit::event::{AxisId, ButtonId, DeviceEvent, DeviceId, ElementState, MouseScrollDelta};
+
+use crate::current_input::PIXELS_PER_LINE;
+use crate::key_set::KeySet;
+
+/// Input state built from `DeviceEvent`s.
+/// Unlike `CurrentInput` this is kept while the window is unfocused.
+#[derive(Clone)]
+pub struct RawInput {
+    pub key_pressed: KeySet,
+    pub key_released: KeySet,
+    pub key_held: KeySet,
+    pub button_actions: Vec<ButtonAction>,
+    pub button_held: Vec<ButtonId>,
+    pub motion: Vec<(AxisId, f64)>,
+    pub y_scroll_diff: f32,
+    pub x_scroll_diff: f32,
+    pub devices_added: Vec<DeviceId>,
+    pub devices_removed: Vec<DeviceId>,
+}
+
+impl RawInput {
+    pub fn new() -> RawInput {
+        RawInput {
+            key_pressed: KeySet::new(),
+            key_released: KeySet::new(),
+            key_held: KeySet::new(),
+            button_actions: vec![],
+            button_held: vec![],
+            motion: vec![],
+            y_scroll_diff: 0.0,
+            x_scroll_diff: 0.0,
+            devices_added: vec![],
+            devices_removed: vec![],
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.key_pressed.clear();
+        self.key_released.clear();
+        self.button_actions.clear();
+        self.motion.clear();
+        self.y_scroll_diff = 0.0;
+        self.x_scroll_diff = 0.0;
+        self.devices_added.clear();
+        self.devices_removed.clear();
+    }
+
+    pub fn handle_device_event(&mut self, device_id: DeviceId, event: &DeviceEvent) {
+        match event {
+            DeviceEvent::Added => self.devices_added.push(device_id),
+            DeviceEvent::Removed => self.devices_removed.push(device_id),
+            DeviceEvent::Key(event) => match event.state {
+                ElementState::Pressed => {
+                    if self.key_held.insert(event.physical_key) {
+                        self.key_pressed.insert(event.physical_key);
+                    }
+                }
+                ElementState::Released => {
+                    self.key_held.remove(event.physical_key);
+                    self.key_released.insert(event.physical_key);
+                }
+            },
+            DeviceEvent::Button { button, state } => match state {
+                ElementState::Pressed => {
+                    if !self.button_held.contains(button) {
+                        self.button_actions.push(ButtonAction::Pressed(*button));
+                        self.button_held.push(*button);
+                    }
+                }
+                ElementState::Released => {
+                    self.button_held.retain(|x| x != button);
+                    self.button_actions.push(ButtonAction::Released(*button));
+                }
+            },
+            DeviceEvent::Motion { axis, value } => {
+                match self.motion.iter_mut().find(|(a, _)| a == axis) {
+                    Some((_, total)) => *total += value,
+                    None => self.motion.push((*axis, *value)),
+                }
+            }
+            DeviceEvent::MouseWheel { delta } => match delta {
+                MouseScrollDelta::LineDelta(x, y) => {
+                    self.x_scroll_diff += x;
+                    self.y_scroll_diff += y;
+                }
+                MouseScrollDelta::PixelDelta(delta) => {
+                    self.y_scroll_diff += (delta.y / PIXELS_PER_LINE) as f32;
+                    self.x_scroll_diff += (delta.x / PIXELS_PER_LINE) as f32;
+                }
+            },
+            DeviceEvent::MouseMotion { .. } => {}
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum ButtonAction {
+    Pressed(ButtonId),
+    Released(ButtonId),
+}
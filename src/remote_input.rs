@@ -0,0 +1,194 @@
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+use crate::tick_input::{DecodeError, TickInput};
+
+/// The input queries shared by `WinitInputHelper`, `RemoteInput` and `TickView`.
+///
+/// Write game logic against this trait to run it with either local or remote input,
+/// e.g. to simulate every player of a networked game in the same way.
+pub trait InputQuery {
+    /// Returns true when the key with the specified keycode goes from "not pressed" to "pressed".
+    /// Otherwise returns false.
+    fn key_pressed(&self, keycode: KeyCode) -> bool;
+
+    /// Returns true when the key with the specified keycode goes from "pressed" to "not pressed".
+    /// Otherwise returns false.
+    fn key_released(&self, keycode: KeyCode) -> bool;
+
+    /// Returns true when the key with the specified keycode remains "pressed".
+    /// Otherwise returns false.
+    fn key_held(&self, keycode: KeyCode) -> bool;
+
+    /// Returns true when the specified mouse button goes from "not pressed" to "pressed".
+    /// Otherwise returns false.
+    fn mouse_pressed(&self, mouse_button: MouseButton) -> bool;
+
+    /// Returns true when the specified mouse button goes from "pressed" to "not pressed".
+    /// Otherwise returns false.
+    fn mouse_released(&self, mouse_button: MouseButton) -> bool;
+
+    /// Returns true while the specified mouse button remains "pressed".
+    /// Otherwise returns false.
+    fn mouse_held(&self, mouse_button: MouseButton) -> bool;
+
+    /// Returns the cursor coordinates in pixels, or `None` if there is no cursor on the window.
+    fn cursor(&self) -> Option<(f32, f32)>;
+
+    /// Returns the change in cursor coordinates since the previous step or tick.
+    fn cursor_diff(&self) -> (f32, f32);
+
+    /// Returns the change in mouse coordinates since the previous step or tick.
+    fn mouse_diff(&self) -> (f32, f32);
+
+    /// Returns the amount scrolled since the previous step or tick.
+    /// Returns (horizontally, vertically)
+    fn scroll_diff(&self) -> (f32, f32);
+}
+
+/// Input received from another machine, one `TickInput` per tick.
+///
+/// Pressed and released are recovered by comparing each tick to the previous one,
+/// so a key that is pressed and released within a single tick on the remote machine is never seen.
+#[derive(Clone, Debug, Default)]
+pub struct RemoteInput {
+    current: TickInput,
+    previous: TickInput,
+}
+
+impl RemoteInput {
+    pub fn new() -> RemoteInput {
+        RemoteInput::default()
+    }
+
+    /// Advances to the next tick, e.g. with input predicted from `tick_input()` or decoded with `TickInput::decode()`.
+    pub fn step(&mut self, input: TickInput) {
+        self.previous = std::mem::replace(&mut self.current, input);
+    }
+
+    /// Decodes the next tick, delta compressed against the current tick, and advances to it.
+    ///
+    /// This requires the ticks to arrive in order and without gaps.
+    /// Over an unreliable transport decode against the last acknowledged tick with `TickInput::decode()` and pass the result to `step()` instead.
+    pub fn receive(&mut self, bytes: &[u8]) -> Result<(), DecodeError> {
+        let input = TickInput::decode(bytes, &self.current)?;
+        self.step(input);
+        Ok(())
+    }
+
+    /// Returns the input of the current tick.
+    pub fn tick_input(&self) -> &TickInput {
+        &self.current
+    }
+
+    fn view(&self) -> TickView<'_> {
+        TickView::new(&self.current, &self.previous)
+    }
+}
+
+impl InputQuery for RemoteInput {
+    fn key_pressed(&self, keycode: KeyCode) -> bool {
+        self.view().key_pressed(keycode)
+    }
+
+    fn key_released(&self, keycode: KeyCode) -> bool {
+        self.view().key_released(keycode)
+    }
+
+    fn key_held(&self, keycode: KeyCode) -> bool {
+        self.view().key_held(keycode)
+    }
+
+    fn mouse_pressed(&self, mouse_button: MouseButton) -> bool {
+        self.view().mouse_pressed(mouse_button)
+    }
+
+    fn mouse_released(&self, mouse_button: MouseButton) -> bool {
+        self.view().mouse_released(mouse_button)
+    }
+
+    fn mouse_held(&self, mouse_button: MouseButton) -> bool {
+        self.view().mouse_held(mouse_button)
+    }
+
+    fn cursor(&self) -> Option<(f32, f32)> {
+        self.view().cursor()
+    }
+
+    fn cursor_diff(&self) -> (f32, f32) {
+        self.view().cursor_diff()
+    }
+
+    fn mouse_diff(&self) -> (f32, f32) {
+        self.view().mouse_diff()
+    }
+
+    fn scroll_diff(&self) -> (f32, f32) {
+        self.view().scroll_diff()
+    }
+}
+
+/// Queries a tick of input, with pressed and released recovered by comparing it to the previous tick.
+///
+/// Returned by `InputHistory::tick()`.
+#[derive(Clone, Copy, Debug)]
+pub struct TickView<'a> {
+    current: &'a TickInput,
+    previous: &'a TickInput,
+}
+
+impl<'a> TickView<'a> {
+    pub(crate) fn new(current: &'a TickInput, previous: &'a TickInput) -> TickView<'a> {
+        TickView { current, previous }
+    }
+
+    /// Returns the input of the tick.
+    pub fn tick_input(&self) -> &'a TickInput {
+        self.current
+    }
+}
+
+impl InputQuery for TickView<'_> {
+    fn key_pressed(&self, keycode: KeyCode) -> bool {
+        self.current.key_held(keycode) && !self.previous.key_held(keycode)
+    }
+
+    fn key_released(&self, keycode: KeyCode) -> bool {
+        !self.current.key_held(keycode) && self.previous.key_held(keycode)
+    }
+
+    fn key_held(&self, keycode: KeyCode) -> bool {
+        self.current.key_held(keycode)
+    }
+
+    fn mouse_pressed(&self, mouse_button: MouseButton) -> bool {
+        self.current.mouse_held(mouse_button) && !self.previous.mouse_held(mouse_button)
+    }
+
+    fn mouse_released(&self, mouse_button: MouseButton) -> bool {
+        !self.current.mouse_held(mouse_button) && self.previous.mouse_held(mouse_button)
+    }
+
+    fn mouse_held(&self, mouse_button: MouseButton) -> bool {
+        self.current.mouse_held(mouse_button)
+    }
+
+    fn cursor(&self) -> Option<(f32, f32)> {
+        self.current.cursor()
+    }
+
+    fn cursor_diff(&self) -> (f32, f32) {
+        match (self.current.cursor(), self.previous.cursor()) {
+            (Some(cur), Some(prev)) => (cur.0 - prev.0, cur.1 - prev.1),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    fn mouse_diff(&self) -> (f32, f32) {
+        self.current.mouse_diff()
+    }
+
+    fn scroll_diff(&self) -> (f32, f32) {
+        self.current.scroll_diff()
+    }
+}
@@ -0,0 +1,194 @@
+use winit::event::MouseButton;
+use winit::keyboard::PhysicalKey;
+use winit::window::Theme;
+
+/// An owned copy of the input state, created by `WinitInputHelper::snapshot()`.
+///
+/// Apart from `focused`, each field holds the value returned by the `WinitInputHelper` method of the same name when the snapshot was taken.
+/// Keys and mouse buttons are in the order they were pressed or released,
+/// but comparing snapshots ignores their order so the same set of held keys is always equal.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputSnapshot {
+    /// False when the window was not focused, in which case the key, mouse and cursor fields are empty.
+    pub focused: bool,
+    pub keys_held: Vec<PhysicalKey>,
+    pub keys_pressed: Vec<PhysicalKey>,
+    pub keys_released: Vec<PhysicalKey>,
+    pub mouse_buttons_held: Vec<MouseButton>,
+    pub mouse_buttons_pressed: Vec<MouseButton>,
+    pub mouse_buttons_released: Vec<MouseButton>,
+    pub cursor: Option<(f32, f32)>,
+    pub cursor_diff: (f32, f32),
+    pub mouse_diff: (f32, f32),
+    pub scroll_diff: (f32, f32),
+    pub resolution: Option<(u32, u32)>,
+    pub scale_factor: Option<f64>,
+    pub window_position: Option<(i32, i32)>,
+    pub occluded: bool,
+    pub theme: Option<Theme>,
+    pub close_requested: bool,
+    pub destroyed: bool,
+}
+
+/// A field of `InputSnapshot`, as returned by `InputSnapshot::diff()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputSnapshotField {
+    Focused,
+    KeysHeld,
+    KeysPressed,
+    KeysReleased,
+    MouseButtonsHeld,
+    MouseButtonsPressed,
+    MouseButtonsReleased,
+    Cursor,
+    CursorDiff,
+    MouseDiff,
+    ScrollDiff,
+    Resolution,
+    ScaleFactor,
+    WindowPosition,
+    Occluded,
+    Theme,
+    CloseRequested,
+    Destroyed,
+}
+
+impl InputSnapshotField {
+    /// Every field in declaration order.
+    pub const ALL: [InputSnapshotField; 18] = [
+        InputSnapshotField::Focused,
+        InputSnapshotField::KeysHeld,
+        InputSnapshotField::KeysPressed,
+        InputSnapshotField::KeysReleased,
+        InputSnapshotField::MouseButtonsHeld,
+        InputSnapshotField::MouseButtonsPressed,
+        InputSnapshotField::MouseButtonsReleased,
+        InputSnapshotField::Cursor,
+        InputSnapshotField::CursorDiff,
+        InputSnapshotField::MouseDiff,
+        InputSnapshotField::ScrollDiff,
+        InputSnapshotField::Resolution,
+        InputSnapshotField::ScaleFactor,
+        InputSnapshotField::WindowPosition,
+        InputSnapshotField::Occluded,
+        InputSnapshotField::Theme,
+        InputSnapshotField::CloseRequested,
+        InputSnapshotField::Destroyed,
+    ];
+}
+
+impl InputSnapshot {
+    /// Returns the fields that differ between the two snapshots, in declaration order.
+    /// Returns an empty `Vec` if the snapshots are equal.
+    pub fn diff(&self, other: &InputSnapshot) -> Vec<InputSnapshotField> {
+        InputSnapshotField::ALL
+            .into_iter()
+            .filter(|field| self.field_changed(other, *field))
+            .collect()
+    }
+
+    fn field_changed(&self, other: &InputSnapshot, field: InputSnapshotField) -> bool {
+        match field {
+            InputSnapshotField::Focused => self.focused != other.focused,
+            InputSnapshotField::KeysHeld => !same_elements(&self.keys_held, &other.keys_held),
+            InputSnapshotField::KeysPressed => {
+                !same_elements(&self.keys_pressed, &other.keys_pressed)
+            }
+            InputSnapshotField::KeysReleased => {
+                !same_elements(&self.keys_released, &other.keys_released)
+            }
+            InputSnapshotField::MouseButtonsHeld => {
+                !same_elements(&self.mouse_buttons_held, &other.mouse_buttons_held)
+            }
+            InputSnapshotField::MouseButtonsPressed => {
+                !same_elements(&self.mouse_buttons_pressed, &other.mouse_buttons_pressed)
+            }
+            InputSnapshotField::MouseButtonsReleased => {
+                !same_elements(&self.mouse_buttons_released, &other.mouse_buttons_released)
+            }
+            InputSnapshotField::Cursor => self.cursor != other.cursor,
+            InputSnapshotField::CursorDiff => self.cursor_diff != other.cursor_diff,
+            InputSnapshotField::MouseDiff => self.mouse_diff != other.mouse_diff,
+            InputSnapshotField::ScrollDiff => self.scroll_diff != other.scroll_diff,
+            InputSnapshotField::Resolution => self.resolution != other.resolution,
+            InputSnapshotField::ScaleFactor => self.scale_factor != other.scale_factor,
+            InputSnapshotField::WindowPosition => self.window_position != other.window_position,
+            InputSnapshotField::Occluded => self.occluded != other.occluded,
+            InputSnapshotField::Theme => self.theme != other.theme,
+            InputSnapshotField::CloseRequested => self.close_requested != other.close_requested,
+            InputSnapshotField::Destroyed => self.destroyed != other.destroyed,
+        }
+    }
+}
+
+impl PartialEq for InputSnapshot {
+    fn eq(&self, other: &InputSnapshot) -> bool {
+        InputSnapshotField::ALL
+            .into_iter()
+            .all(|field| !self.field_changed(other, field))
+    }
+}
+
+/// Returns true if the slices contain the same elements the same number of times, in any order.
+///
+/// Snapshots taken by `WinitInputHelper` never contain duplicates, but deserialized or hand built ones can,
+/// so the counts are compared to keep `PartialEq` symmetric.
+fn same_elements<T: PartialEq>(a: &[T], b: &[T]) -> bool {
+    let count = |slice: &[T], x: &T| slice.iter().filter(|y| *y == x).count();
+    a.len() == b.len() && a.iter().all(|x| count(a, x) == count(b, x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::keyboard::KeyCode;
+
+    const A: PhysicalKey = PhysicalKey::Code(KeyCode::KeyA);
+    const B: PhysicalKey = PhysicalKey::Code(KeyCode::KeyB);
+
+    fn with_keys_held(keys_held: Vec<PhysicalKey>) -> InputSnapshot {
+        InputSnapshot {
+            keys_held,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn key_order_is_ignored() {
+        let a = with_keys_held(vec![A, B]);
+        let b = with_keys_held(vec![B, A]);
+        assert_eq!(a, b);
+        assert!(a.diff(&b).is_empty());
+    }
+
+    #[test]
+    fn duplicates_compare_symmetrically() {
+        let a = with_keys_held(vec![A, A]);
+        let b = with_keys_held(vec![A, B]);
+        assert_ne!(a, b);
+        assert_ne!(b, a);
+        assert_eq!(a.diff(&b), [InputSnapshotField::KeysHeld]);
+        assert_eq!(b.diff(&a), [InputSnapshotField::KeysHeld]);
+    }
+
+    #[test]
+    fn diff_lists_changed_fields_in_declaration_order() {
+        let a = InputSnapshot::default();
+        let b = InputSnapshot {
+            destroyed: true,
+            cursor: Some((1.0, 2.0)),
+            mouse_buttons_held: vec![MouseButton::Left],
+            ..Default::default()
+        };
+        assert_eq!(
+            a.diff(&b),
+            [
+                InputSnapshotField::MouseButtonsHeld,
+                InputSnapshotField::Cursor,
+                InputSnapshotField::Destroyed,
+            ]
+        );
+    }
+}
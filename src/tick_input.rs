@@ -0,0 +1,708 @@
+use std::fmt;
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+/// Cursor positions, mouse diffs and scroll diffs are quantized to this fraction of a pixel or line.
+const QUANTIZATION_STEPS: f32 = 64.0;
+
+const KEYS_CHANGED: u8 = 1 << 0;
+const MOUSE_CHANGED: u8 = 1 << 1;
+const CURSOR_CHANGED: u8 = 1 << 2;
+const MOUSE_DIFF_CHANGED: u8 = 1 << 3;
+const SCROLL_DIFF_CHANGED: u8 = 1 << 4;
+
+/// The held keys, held mouse buttons, cursor and diffs of a single tick, in a form that can be sent over the network.
+///
+/// Create one from local input with `WinitInputHelper::tick_input()`, or build one with the setters, e.g. for bots.
+/// Pressed and released are not stored, they are recovered by comparing consecutive ticks, see `RemoteInput`.
+///
+/// Values are quantized to 1/64 of a pixel or line when they are set,
+/// so a decoded `TickInput` is always equal to the one that was encoded and simulations using it stay deterministic.
+/// `PhysicalKey::Unidentified` keys are not stored.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TickInput {
+    keys: [u64; 4],
+    /// Sorted by `mouse_button_id()`.
+    mouse_held: Vec<MouseButton>,
+    cursor: Option<(f32, f32)>,
+    mouse_diff: (f32, f32),
+    scroll_diff: (f32, f32),
+}
+
+impl TickInput {
+    pub fn new() -> TickInput {
+        TickInput::default()
+    }
+
+    /// Returns true while the key with the specified keycode is "pressed".
+    /// Otherwise returns false.
+    pub fn key_held(&self, keycode: KeyCode) -> bool {
+        match key_index(keycode) {
+            Some(index) => self.keys[index / 64] & 1 << (index % 64) != 0,
+            None => false,
+        }
+    }
+
+    pub fn set_key_held(&mut self, keycode: KeyCode, held: bool) {
+        if let Some(index) = key_index(keycode) {
+            if held {
+                self.keys[index / 64] |= 1 << (index % 64);
+            } else {
+                self.keys[index / 64] &= !(1 << (index % 64));
+            }
+        }
+    }
+
+    /// Returns every key that is "pressed", in `KeyCode` declaration order.
+    pub fn keys_held(&self) -> impl Iterator<Item = KeyCode> + '_ {
+        KEY_CODES
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.keys[index / 64] & 1 << (index % 64) != 0)
+            .map(|(_, keycode)| *keycode)
+    }
+
+    /// Returns true while the specified mouse button is "pressed".
+    /// Otherwise returns false.
+    pub fn mouse_held(&self, mouse_button: MouseButton) -> bool {
+        self.mouse_held.contains(&mouse_button)
+    }
+
+    pub fn set_mouse_held(&mut self, mouse_button: MouseButton, held: bool) {
+        let id = mouse_button_id(mouse_button);
+        match self
+            .mouse_held
+            .binary_search_by_key(&id, |button| mouse_button_id(*button))
+        {
+            Ok(i) if !held => {
+                self.mouse_held.remove(i);
+            }
+            Err(i) if held => self.mouse_held.insert(i, mouse_button),
+            _ => {}
+        }
+    }
+
+    /// Returns every mouse button that is "pressed".
+    pub fn mouse_buttons_held(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        self.mouse_held.iter().copied()
+    }
+
+    pub fn cursor(&self) -> Option<(f32, f32)> {
+        self.cursor
+    }
+
+    pub fn set_cursor(&mut self, cursor: Option<(f32, f32)>) {
+        self.cursor = cursor.map(quantize_pair);
+    }
+
+    pub fn mouse_diff(&self) -> (f32, f32) {
+        self.mouse_diff
+    }
+
+    pub fn set_mouse_diff(&mut self, mouse_diff: (f32, f32)) {
+        self.mouse_diff = quantize_pair(mouse_diff);
+    }
+
+    pub fn scroll_diff(&self) -> (f32, f32) {
+        self.scroll_diff
+    }
+
+    pub fn set_scroll_diff(&mut self, scroll_diff: (f32, f32)) {
+        self.scroll_diff = quantize_pair(scroll_diff);
+    }
+
+    /// Appends the binary encoding of this tick to `out`, delta compressed against `baseline`.
+    ///
+    /// Held keys and buttons are sent as the ones that toggled since the baseline, the cursor as the distance moved since the baseline,
+    /// and fields that are unchanged are skipped entirely, so an idle tick encodes to a single byte.
+    /// The receiver must decode with the same baseline, usually the previous tick or the last tick it acknowledged.
+    /// Use `TickInput::new()` as the baseline to encode a tick on its own.
+    pub fn encode(&self, baseline: &TickInput, out: &mut Vec<u8>) {
+        let mut changed = 0;
+        if self.keys != baseline.keys {
+            changed |= KEYS_CHANGED;
+        }
+        if self.mouse_held != baseline.mouse_held {
+            changed |= MOUSE_CHANGED;
+        }
+        if self.cursor != baseline.cursor {
+            changed |= CURSOR_CHANGED;
+        }
+        if self.mouse_diff != baseline.mouse_diff {
+            changed |= MOUSE_DIFF_CHANGED;
+        }
+        if self.scroll_diff != baseline.scroll_diff {
+            changed |= SCROLL_DIFF_CHANGED;
+        }
+        out.push(changed);
+
+        if changed & KEYS_CHANGED != 0 {
+            let toggled: Vec<usize> = (0..KEY_CODES.len())
+                .filter(|index| {
+                    (self.keys[index / 64] ^ baseline.keys[index / 64]) & 1 << (index % 64) != 0
+                })
+                .collect();
+            write_varint(out, toggled.len() as u32);
+            out.extend(toggled.iter().map(|index| *index as u8));
+        }
+        if changed & MOUSE_CHANGED != 0 {
+            let toggled: Vec<u32> = self
+                .mouse_held
+                .iter()
+                .filter(|button| !baseline.mouse_held.contains(button))
+                .chain(
+                    baseline
+                        .mouse_held
+                        .iter()
+                        .filter(|button| !self.mouse_held.contains(button)),
+                )
+                .map(|button| mouse_button_id(*button))
+                .collect();
+            write_varint(out, toggled.len() as u32);
+            for id in toggled {
+                write_varint(out, id);
+            }
+        }
+        if changed & CURSOR_CHANGED != 0 {
+            match self.cursor {
+                Some(cursor) => {
+                    out.push(1);
+                    let origin = baseline.cursor.unwrap_or((0.0, 0.0));
+                    write_fixed(out, cursor.0, origin.0);
+                    write_fixed(out, cursor.1, origin.1);
+                }
+                None => out.push(0),
+            }
+        }
+        if changed & MOUSE_DIFF_CHANGED != 0 {
+            write_fixed(out, self.mouse_diff.0, 0.0);
+            write_fixed(out, self.mouse_diff.1, 0.0);
+        }
+        if changed & SCROLL_DIFF_CHANGED != 0 {
+            write_fixed(out, self.scroll_diff.0, 0.0);
+            write_fixed(out, self.scroll_diff.1, 0.0);
+        }
+    }
+
+    /// Decodes a tick encoded by `TickInput::encode()` with the same `baseline`.
+    ///
+    /// Returns an error if `bytes` is not exactly one encoded tick.
+    pub fn decode(bytes: &[u8], baseline: &TickInput) -> Result<TickInput, DecodeError> {
+        let mut reader = Reader { bytes };
+        let mut tick = baseline.clone();
+        let changed = reader.byte()?;
+
+        if changed & KEYS_CHANGED != 0 {
+            for _ in 0..reader.varint()? {
+                let index = reader.byte()? as usize;
+                if index >= KEY_CODES.len() {
+                    return Err(DecodeError::InvalidKey(index as u8));
+                }
+                tick.keys[index / 64] ^= 1 << (index % 64);
+            }
+        }
+        if changed & MOUSE_CHANGED != 0 {
+            for _ in 0..reader.varint()? {
+                let id = reader.varint()?;
+                let button = mouse_button_from_id(id).ok_or(DecodeError::InvalidMouseButton(id))?;
+                let held = tick.mouse_held(button);
+                tick.set_mouse_held(button, !held);
+            }
+        }
+        if changed & CURSOR_CHANGED != 0 {
+            tick.cursor = match reader.byte()? {
+                0 => None,
+                1 => {
+                    let origin = baseline.cursor.unwrap_or((0.0, 0.0));
+                    Some((reader.fixed(origin.0)?, reader.fixed(origin.1)?))
+                }
+                _ => return Err(DecodeError::InvalidData),
+            };
+        }
+        if changed & MOUSE_DIFF_CHANGED != 0 {
+            tick.mouse_diff = (reader.fixed(0.0)?, reader.fixed(0.0)?);
+        }
+        if changed & SCROLL_DIFF_CHANGED != 0 {
+            tick.scroll_diff = (reader.fixed(0.0)?, reader.fixed(0.0)?);
+        }
+
+        if reader.bytes.is_empty() {
+            Ok(tick)
+        } else {
+            Err(DecodeError::InvalidData)
+        }
+    }
+}
+
+/// The error returned by `TickInput::decode()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data ended in the middle of a tick.
+    UnexpectedEnd,
+    /// The data contains a key this version of winit_input_helper does not know about.
+    InvalidKey(u8),
+    /// The data contains a mouse button id beyond the range of `MouseButton::Other`.
+    InvalidMouseButton(u32),
+    /// The data is not an encoded tick, or has bytes left over after the tick.
+    InvalidData,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "tick input ended unexpectedly"),
+            DecodeError::InvalidKey(index) => write!(f, "invalid key index {index} in tick input"),
+            DecodeError::InvalidMouseButton(id) => {
+                write!(f, "invalid mouse button id {id} in tick input")
+            }
+            DecodeError::InvalidData => write!(f, "invalid tick input"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        let (first, rest) = self.bytes.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+        self.bytes = rest;
+        Ok(*first)
+    }
+
+    fn varint(&mut self) -> Result<u32, DecodeError> {
+        let mut value = 0u32;
+        for shift in (0..32).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u32) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::InvalidData)
+    }
+
+    fn fixed(&mut self, origin: f32) -> Result<f32, DecodeError> {
+        let zigzag = self.varint()?;
+        let delta = (zigzag >> 1) as i32 ^ -((zigzag & 1) as i32);
+        Ok((quantize(origin).wrapping_add(delta)) as f32 / QUANTIZATION_STEPS)
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Writes the difference between the quantized values as a zigzag encoded varint, so small values take a single byte.
+fn write_fixed(out: &mut Vec<u8>, value: f32, origin: f32) {
+    let delta = quantize(value).wrapping_sub(quantize(origin));
+    write_varint(out, ((delta << 1) ^ (delta >> 31)) as u32);
+}
+
+fn quantize(value: f32) -> i32 {
+    (value * QUANTIZATION_STEPS).round() as i32
+}
+
+fn quantize_pair(value: (f32, f32)) -> (f32, f32) {
+    (
+        quantize(value.0) as f32 / QUANTIZATION_STEPS,
+        quantize(value.1) as f32 / QUANTIZATION_STEPS,
+    )
+}
+
+fn mouse_button_id(button: MouseButton) -> u32 {
+    match button {
+        MouseButton::Left => 0,
+        MouseButton::Right => 1,
+        MouseButton::Middle => 2,
+        MouseButton::Back => 3,
+        MouseButton::Forward => 4,
+        MouseButton::Other(id) => 5 + id as u32,
+    }
+}
+
+fn mouse_button_from_id(id: u32) -> Option<MouseButton> {
+    match id {
+        0 => Some(MouseButton::Left),
+        1 => Some(MouseButton::Right),
+        2 => Some(MouseButton::Middle),
+        3 => Some(MouseButton::Back),
+        4 => Some(MouseButton::Forward),
+        _ => u16::try_from(id - 5).ok().map(MouseButton::Other),
+    }
+}
+
+/// Returns `None` for keys added to winit after this table was written, so they are never sent.
+fn key_index(keycode: KeyCode) -> Option<usize> {
+    let index = keycode as usize;
+    match KEY_CODES.get(index) {
+        Some(entry) if *entry == keycode => Some(index),
+        _ => None,
+    }
+}
+
+/// Every `KeyCode` in declaration order, so the index of a key is its discriminant.
+/// This is the wire format for keys and must only ever be appended to.
+const KEY_CODES: [KeyCode; 194] = [
+    KeyCode::Backquote,
+    KeyCode::Backslash,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::Comma,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::Equal,
+    KeyCode::IntlBackslash,
+    KeyCode::IntlRo,
+    KeyCode::IntlYen,
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Minus,
+    KeyCode::Period,
+    KeyCode::Quote,
+    KeyCode::Semicolon,
+    KeyCode::Slash,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::Backspace,
+    KeyCode::CapsLock,
+    KeyCode::ContextMenu,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::Enter,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::Space,
+    KeyCode::Tab,
+    KeyCode::Convert,
+    KeyCode::KanaMode,
+    KeyCode::Lang1,
+    KeyCode::Lang2,
+    KeyCode::Lang3,
+    KeyCode::Lang4,
+    KeyCode::Lang5,
+    KeyCode::NonConvert,
+    KeyCode::Delete,
+    KeyCode::End,
+    KeyCode::Help,
+    KeyCode::Home,
+    KeyCode::Insert,
+    KeyCode::PageDown,
+    KeyCode::PageUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::ArrowUp,
+    KeyCode::NumLock,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::NumpadAdd,
+    KeyCode::NumpadBackspace,
+    KeyCode::NumpadClear,
+    KeyCode::NumpadClearEntry,
+    KeyCode::NumpadComma,
+    KeyCode::NumpadDecimal,
+    KeyCode::NumpadDivide,
+    KeyCode::NumpadEnter,
+    KeyCode::NumpadEqual,
+    KeyCode::NumpadHash,
+    KeyCode::NumpadMemoryAdd,
+    KeyCode::NumpadMemoryClear,
+    KeyCode::NumpadMemoryRecall,
+    KeyCode::NumpadMemoryStore,
+    KeyCode::NumpadMemorySubtract,
+    KeyCode::NumpadMultiply,
+    KeyCode::NumpadParenLeft,
+    KeyCode::NumpadParenRight,
+    KeyCode::NumpadStar,
+    KeyCode::NumpadSubtract,
+    KeyCode::Escape,
+    KeyCode::Fn,
+    KeyCode::FnLock,
+    KeyCode::PrintScreen,
+    KeyCode::ScrollLock,
+    KeyCode::Pause,
+    KeyCode::BrowserBack,
+    KeyCode::BrowserFavorites,
+    KeyCode::BrowserForward,
+    KeyCode::BrowserHome,
+    KeyCode::BrowserRefresh,
+    KeyCode::BrowserSearch,
+    KeyCode::BrowserStop,
+    KeyCode::Eject,
+    KeyCode::LaunchApp1,
+    KeyCode::LaunchApp2,
+    KeyCode::LaunchMail,
+    KeyCode::MediaPlayPause,
+    KeyCode::MediaSelect,
+    KeyCode::MediaStop,
+    KeyCode::MediaTrackNext,
+    KeyCode::MediaTrackPrevious,
+    KeyCode::Power,
+    KeyCode::Sleep,
+    KeyCode::AudioVolumeDown,
+    KeyCode::AudioVolumeMute,
+    KeyCode::AudioVolumeUp,
+    KeyCode::WakeUp,
+    KeyCode::Meta,
+    KeyCode::Hyper,
+    KeyCode::Turbo,
+    KeyCode::Abort,
+    KeyCode::Resume,
+    KeyCode::Suspend,
+    KeyCode::Again,
+    KeyCode::Copy,
+    KeyCode::Cut,
+    KeyCode::Find,
+    KeyCode::Open,
+    KeyCode::Paste,
+    KeyCode::Props,
+    KeyCode::Select,
+    KeyCode::Undo,
+    KeyCode::Hiragana,
+    KeyCode::Katakana,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::F13,
+    KeyCode::F14,
+    KeyCode::F15,
+    KeyCode::F16,
+    KeyCode::F17,
+    KeyCode::F18,
+    KeyCode::F19,
+    KeyCode::F20,
+    KeyCode::F21,
+    KeyCode::F22,
+    KeyCode::F23,
+    KeyCode::F24,
+    KeyCode::F25,
+    KeyCode::F26,
+    KeyCode::F27,
+    KeyCode::F28,
+    KeyCode::F29,
+    KeyCode::F30,
+    KeyCode::F31,
+    KeyCode::F32,
+    KeyCode::F33,
+    KeyCode::F34,
+    KeyCode::F35,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(tick: &TickInput, baseline: &TickInput) -> Vec<u8> {
+        let mut bytes = vec![];
+        tick.encode(baseline, &mut bytes);
+        assert_eq!(TickInput::decode(&bytes, baseline).as_ref(), Ok(tick));
+        bytes
+    }
+
+    fn busy_tick() -> TickInput {
+        let mut tick = TickInput::new();
+        tick.set_key_held(KeyCode::KeyW, true);
+        tick.set_key_held(KeyCode::ShiftLeft, true);
+        tick.set_key_held(KeyCode::F35, true);
+        tick.set_mouse_held(MouseButton::Left, true);
+        tick.set_mouse_held(MouseButton::Other(u16::MAX), true);
+        tick.set_cursor(Some((1234.567, -3.3)));
+        tick.set_mouse_diff((0.5, -1.25));
+        tick.set_scroll_diff((0.0, 3.0));
+        tick
+    }
+
+    #[test]
+    fn round_trip_against_empty_baseline() {
+        round_trip(&TickInput::new(), &TickInput::new());
+        round_trip(&busy_tick(), &TickInput::new());
+    }
+
+    #[test]
+    fn round_trip_against_previous_tick() {
+        let baseline = busy_tick();
+        let mut tick = baseline.clone();
+        tick.set_key_held(KeyCode::KeyW, false);
+        tick.set_key_held(KeyCode::KeyA, true);
+        tick.set_mouse_held(MouseButton::Left, false);
+        tick.set_mouse_held(MouseButton::Other(250), true);
+        tick.set_cursor(Some((1240.0, -3.3)));
+        tick.set_mouse_diff((0.0, 0.0));
+        round_trip(&tick, &baseline);
+
+        // Releasing everything and moving the cursor off the window
+        round_trip(&TickInput::new(), &baseline);
+        // Moving the cursor onto the window
+        round_trip(&baseline, &TickInput::new());
+    }
+
+    #[test]
+    fn idle_tick_is_one_byte() {
+        let tick = busy_tick();
+        assert_eq!(round_trip(&tick, &tick), [0]);
+        assert_eq!(round_trip(&TickInput::new(), &TickInput::new()), [0]);
+    }
+
+    #[test]
+    fn extreme_values_round_trip() {
+        let mut baseline = TickInput::new();
+        baseline.set_cursor(Some((f32::NEG_INFINITY, f32::MAX)));
+        let mut tick = TickInput::new();
+        tick.set_cursor(Some((f32::INFINITY, f32::MIN)));
+        tick.set_mouse_diff((f32::NAN, -f32::MAX));
+        tick.set_scroll_diff((f32::MIN_POSITIVE, -0.0));
+        round_trip(&tick, &baseline);
+        round_trip(&tick, &TickInput::new());
+        round_trip(&baseline, &tick);
+    }
+
+    #[test]
+    fn setters_quantize() {
+        let mut tick = TickInput::new();
+        tick.set_mouse_diff((0.005, 1.0 / 64.0 + 0.005));
+        assert_eq!(tick.mouse_diff(), (0.0, 1.0 / 64.0));
+    }
+
+    #[test]
+    fn truncated_input_is_unexpected_end() {
+        let mut bytes = vec![];
+        busy_tick().encode(&TickInput::new(), &mut bytes);
+        for len in 0..bytes.len() {
+            assert_eq!(
+                TickInput::decode(&bytes[..len], &TickInput::new()),
+                Err(DecodeError::UnexpectedEnd),
+                "truncated to {len} bytes"
+            );
+        }
+    }
+
+    #[test]
+    fn trailing_bytes_are_invalid() {
+        let mut bytes = vec![];
+        busy_tick().encode(&TickInput::new(), &mut bytes);
+        bytes.push(0);
+        assert_eq!(
+            TickInput::decode(&bytes, &TickInput::new()),
+            Err(DecodeError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn out_of_range_key_is_invalid() {
+        let index = KEY_CODES.len() as u8;
+        assert_eq!(
+            TickInput::decode(&[KEYS_CHANGED, 1, index], &TickInput::new()),
+            Err(DecodeError::InvalidKey(index))
+        );
+        assert_eq!(
+            TickInput::decode(&[KEYS_CHANGED, 1, u8::MAX], &TickInput::new()),
+            Err(DecodeError::InvalidKey(u8::MAX))
+        );
+    }
+
+    #[test]
+    fn out_of_range_mouse_button_is_invalid() {
+        let id = mouse_button_id(MouseButton::Other(u16::MAX)) + 1;
+        let mut bytes = vec![MOUSE_CHANGED, 1];
+        write_varint(&mut bytes, id);
+        assert_eq!(
+            TickInput::decode(&bytes, &TickInput::new()),
+            Err(DecodeError::InvalidMouseButton(id))
+        );
+    }
+
+    #[test]
+    fn unknown_cursor_tag_is_invalid() {
+        assert_eq!(
+            TickInput::decode(&[CURSOR_CHANGED, 2], &TickInput::new()),
+            Err(DecodeError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn overlong_varint_is_invalid() {
+        assert_eq!(
+            TickInput::decode(
+                &[KEYS_CHANGED, 0x80, 0x80, 0x80, 0x80, 0x80],
+                &TickInput::new()
+            ),
+            Err(DecodeError::InvalidData)
+        );
+    }
+
+    /// The wire format sends a key as its index in `KEY_CODES`, if winit reorders `KeyCode` this must fail rather than silently change the format.
+    #[test]
+    fn key_codes_match_discriminants() {
+        for (index, keycode) in KEY_CODES.iter().enumerate() {
+            assert_eq!(key_index(*keycode), Some(index), "{keycode:?}");
+            assert_eq!(*keycode as usize, index, "{keycode:?}");
+        }
+    }
+
+    #[test]
+    fn every_mouse_button_id_round_trips() {
+        for id in 0..=mouse_button_id(MouseButton::Other(u16::MAX)) {
+            let button = mouse_button_from_id(id).unwrap();
+            assert_eq!(mouse_button_id(button), id);
+        }
+    }
+}
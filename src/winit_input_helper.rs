@@ -1,12 +1,40 @@
-use winit::dpi::PhysicalSize;
-use winit::event::{DeviceEvent, MouseButton, WindowEvent};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::{
+    AxisId, ButtonId, DeviceEvent, DeviceId, ElementState, MouseButton, TouchPhase, WindowEvent,
+};
+use winit::event_loop::AsyncRequestSerial;
 use winit::keyboard::{Key, KeyCode, PhysicalKey};
+use winit::window::{ActivationToken, Theme};
 
-use crate::current_input::{
-    mouse_button_to_int, CurrentInput, KeyAction, MouseAction, ScanCodeAction,
+use crate::consumption::Consumption;
+use crate::current_input::{CurrentInput, CursorSample, KeyAction, MouseAction, Pen};
+use crate::frame_stats::FrameStats;
+#[cfg(feature = "gilrs")]
+use crate::gamepad::{GamepadButtonAction, GamepadInput, Rumble};
+use crate::input_context::{
+    any_binding, apply_context_change, Binding, ContextChange, InputContext,
 };
+use crate::input_kind::{mouse_motion_input_kind, window_event_input_kind, InputKind};
+use crate::mouse_processing::MouseProcessing;
+use crate::raw_input::{ButtonAction, RawInput};
+use crate::remote_input::InputQuery;
+use crate::snapshot::InputSnapshot;
+use crate::tick_input::TickInput;
 use std::{path::PathBuf, time::Duration};
 use web_time::Instant;
+
+#[cfg(feature = "gilrs")]
+use gilrs::{Axis, Button, GamepadId, Gilrs};
+
+/// `cursor_velocity()` is estimated from the cursor samples received within this duration.
+const CURSOR_VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+/// The fixed timestep accumulator is capped at this many ticks.
+const MAX_ACCUMULATED_TICKS: u32 = 10;
+
+/// Simulated momentum scrolling stops once its velocity (in lines per second) falls below this value.
+const KINETIC_SCROLL_MIN_VELOCITY: f32 = 0.1;
+
 /// The main struct of the API.
 ///
 /// Create with `WinitInputHelper::new`.
@@ -14,6 +42,11 @@ use web_time::Instant;
 /// * Call `WinitInputHelper::process_device_event()` every time ApplicationHandler.device_event() is called.
 /// * Call `WinitInputHelper::step()` every time ApplicationHandler.new_events() is called.
 /// * Call `WinitInputHelper::end_step()` every time ApplicationHandler.about_to_wait() is called.
+/// * Optionally call `WinitInputHelper::process_suspended()`, `WinitInputHelper::process_resumed()` and `WinitInputHelper::process_memory_warning()` in the corresponding ApplicationHandler methods.
+///
+/// When the `gilrs` feature is enabled gamepads are polled during `WinitInputHelper::step()`.
+/// Cloning a `WinitInputHelper` keeps the current gamepad state, but the clone never polls gamepads again and `gamepad_rumble()` on it returns `gilrs::ff::Error::Disconnected`.
+/// Call `WinitInputHelper::set_gilrs()` on the clone to give it its own `Gilrs` instance.
 ///
 /// It is crucial that you call all of these functions every time they are required to be called:
 ///
@@ -27,15 +60,52 @@ use web_time::Instant;
 #[derive(Clone)]
 pub struct WinitInputHelper {
     current: Option<CurrentInput>,
-    dropped_file: Option<PathBuf>,
+    fixed_timestep: Option<Duration>,
+    tick_accumulator: Duration,
+    tick_pending: CurrentInput,
+    tick_current: CurrentInput,
+    in_tick: bool,
+    raw: RawInput,
+    #[cfg(feature = "gilrs")]
+    gamepads: GamepadInput,
+    devices: Option<Vec<(DeviceId, CurrentInput)>>,
+    dropped_files: Vec<PathBuf>,
+    hovered_files: Vec<PathBuf>,
+    file_hover_cancelled: bool,
     window_resized: Option<PhysicalSize<u32>>,
     window_size: Option<(u32, u32)>,
     scale_factor_changed: Option<f64>,
     scale_factor: Option<f64>,
+    window_moved: Option<PhysicalPosition<i32>>,
+    window_position: Option<(i32, i32)>,
+    occluded: bool,
+    theme_changed: Option<Theme>,
+    theme: Option<Theme>,
+    activation_tokens: Vec<(AsyncRequestSerial, ActivationToken)>,
     destroyed: bool,
     close_requested: bool,
+    suspended: bool,
+    resumed: bool,
+    memory_warning: bool,
     step_start: Option<Instant>,
     step_duration: Option<Duration>,
+    step_jitter: Option<Duration>,
+    frame_stats: FrameStats,
+    input_latency: Option<Duration>,
+    kinetic_scroll_friction: Option<f32>,
+    kinetic_scroll_velocity: (f32, f32),
+    kinetic_scroll_active: bool,
+    mouse_processing: MouseProcessing,
+    mouse_diff_processed: (f32, f32),
+    consumption: Consumption,
+    contexts: Vec<InputContext>,
+    press_history: Vec<(Binding, Instant)>,
+    press_history_duration: Duration,
+    context_changes: Vec<ContextChange>,
+    input_layer: u32,
+    last_input_kind: InputKind,
+    input_kind_changed: bool,
+    input_kind_motion_threshold: f32,
 }
 
 impl Default for WinitInputHelper {
@@ -48,31 +118,102 @@ impl WinitInputHelper {
     pub fn new() -> WinitInputHelper {
         WinitInputHelper {
             current: Some(CurrentInput::new()),
-            dropped_file: None,
+            fixed_timestep: None,
+            tick_accumulator: Duration::ZERO,
+            tick_pending: CurrentInput::new(),
+            tick_current: CurrentInput::new(),
+            in_tick: false,
+            raw: RawInput::new(),
+            #[cfg(feature = "gilrs")]
+            gamepads: GamepadInput::new(),
+            devices: None,
+            dropped_files: vec![],
+            hovered_files: vec![],
+            file_hover_cancelled: false,
             window_resized: None,
             window_size: None,
             scale_factor_changed: None,
             scale_factor: None,
+            window_moved: None,
+            window_position: None,
+            occluded: false,
+            theme_changed: None,
+            theme: None,
+            activation_tokens: vec![],
             destroyed: false,
             close_requested: false,
+            suspended: false,
+            resumed: false,
+            memory_warning: false,
             step_start: None,
             step_duration: None,
+            step_jitter: None,
+            frame_stats: FrameStats::new(),
+            input_latency: None,
+            kinetic_scroll_friction: None,
+            kinetic_scroll_velocity: (0.0, 0.0),
+            kinetic_scroll_active: false,
+            mouse_processing: MouseProcessing::default(),
+            mouse_diff_processed: (0.0, 0.0),
+            consumption: Consumption::new(),
+            contexts: vec![],
+            press_history: vec![],
+            press_history_duration: Duration::from_secs(1),
+            context_changes: vec![],
+            input_layer: 0,
+            last_input_kind: InputKind::KeyboardMouse,
+            input_kind_changed: false,
+            input_kind_motion_threshold: 4.0,
         }
     }
 
     /// Call every time ApplicationHandler.new_events() is called.
     /// Clears all internal state.
     pub fn step(&mut self) {
-        self.dropped_file = None;
+        self.dropped_files.clear();
+        self.file_hover_cancelled = false;
         self.window_resized = None;
         self.scale_factor_changed = None;
+        self.window_moved = None;
+        self.theme_changed = None;
+        self.activation_tokens.clear();
         self.close_requested = false;
+        self.resumed = false;
+        self.memory_warning = false;
+        self.input_kind_changed = false;
+        self.consumption.step();
+        for change in self.context_changes.drain(..) {
+            apply_context_change(&mut self.contexts, change);
+        }
+        let press_history_duration = self.press_history_duration;
+        self.press_history
+            .retain(|(_, time)| time.elapsed() <= press_history_duration);
         // Set the start time on the first event to avoid the first step appearing too long
         self.step_start.get_or_insert(Instant::now());
         self.step_duration = None;
         if let Some(current) = &mut self.current {
             current.step();
         }
+        self.raw.step();
+        #[cfg(feature = "gilrs")]
+        {
+            self.gamepads.step();
+            if let Some(gamepad) = self.gamepads.gamepads.iter().find(|g| g.active) {
+                self.set_input_kind(InputKind::Gamepad(gamepad.id));
+            }
+            let now = Instant::now();
+            for gamepad in &self.gamepads.gamepads {
+                for action in &gamepad.button_actions {
+                    if let GamepadButtonAction::Pressed(button) = action {
+                        self.press_history
+                            .push((Binding::GamepadButton(*button), now));
+                    }
+                }
+            }
+        }
+        for (_, device) in self.devices.iter_mut().flatten() {
+            device.step();
+        }
     }
 
     /// Call every time ApplicationHandler.window_event() is called.
@@ -87,13 +228,32 @@ impl WinitInputHelper {
         match event {
             WindowEvent::CloseRequested => self.close_requested = true,
             WindowEvent::Destroyed => self.destroyed = true,
-            WindowEvent::Focused(false) => self.current = None,
+            WindowEvent::Focused(false) => {
+                self.current = None;
+                self.press_history.clear();
+                self.tick_pending = CurrentInput::new();
+                if let Some(devices) = &mut self.devices {
+                    devices.clear();
+                }
+            }
             WindowEvent::Focused(true) => {
                 if self.current.is_none() {
                     self.current = Some(CurrentInput::new())
                 }
             }
-            WindowEvent::DroppedFile(path) => self.dropped_file = Some(path.clone()),
+            WindowEvent::DroppedFile(path) => {
+                self.hovered_files.clear();
+                self.dropped_files.push(path.clone());
+            }
+            WindowEvent::HoveredFile(path) => {
+                if !self.hovered_files.contains(path) {
+                    self.hovered_files.push(path.clone());
+                }
+            }
+            WindowEvent::HoveredFileCancelled => {
+                self.hovered_files.clear();
+                self.file_hover_cancelled = true;
+            }
             WindowEvent::Resized(size) => {
                 self.window_resized = Some(*size);
                 self.window_size = Some((*size).into());
@@ -102,6 +262,18 @@ impl WinitInputHelper {
                 self.scale_factor_changed = Some(*scale_factor);
                 self.scale_factor = Some(*scale_factor);
             }
+            WindowEvent::Moved(position) => {
+                self.window_moved = Some(*position);
+                self.window_position = Some((*position).into());
+            }
+            WindowEvent::Occluded(occluded) => self.occluded = *occluded,
+            WindowEvent::ThemeChanged(theme) => {
+                self.theme_changed = Some(*theme);
+                self.theme = Some(*theme);
+            }
+            WindowEvent::ActivationTokenDone { serial, token } => {
+                self.activation_tokens.push((*serial, token.clone()));
+            }
             WindowEvent::RedrawRequested => {
                 received_redraw_request = true;
             }
@@ -109,23 +281,305 @@ impl WinitInputHelper {
         }
         if let Some(current) = &mut self.current {
             current.handle_event(event);
+            if self.fixed_timestep.is_some() {
+                self.tick_pending.handle_event(event);
+            }
+            if let Some(kind) =
+                window_event_input_kind(event, current, self.input_kind_motion_threshold)
+            {
+                self.set_input_kind(kind);
+            }
+            if let Some(binding) = Binding::pressed_by(event) {
+                self.press_history.push((binding, Instant::now()));
+            }
+
+            if let Some(device_id) = window_event_device_id(event) {
+                if let Some(device) = self.device_mut(device_id) {
+                    device.handle_event(event);
+                }
+            }
         }
         received_redraw_request
     }
 
     /// Call every time ApplicationHandler.device_event() is called.
-    /// Updates value of `mouse_diff()`.
-    pub fn process_device_event(&mut self, event: &DeviceEvent) {
+    /// Updates value of `mouse_diff()` and the `raw_*` accessor methods.
+    pub fn process_device_event(&mut self, device_id: DeviceId, event: &DeviceEvent) {
         if let Some(ref mut current) = self.current {
             current.handle_device_event(event);
+            if self.fixed_timestep.is_some() {
+                self.tick_pending.handle_device_event(event);
+            }
+            if let Some(kind) = mouse_motion_input_kind(current, self.input_kind_motion_threshold) {
+                self.set_input_kind(kind);
+            }
+
+            if let DeviceEvent::Removed = event {
+                if let Some(devices) = &mut self.devices {
+                    devices.retain(|(id, _)| *id != device_id);
+                }
+            } else if let Some(device) = self.device_mut(device_id) {
+                device.handle_device_event(event);
+            }
+        }
+        self.raw.handle_device_event(device_id, event);
+    }
+
+    /// Records the physical key of a `WindowEvent::KeyboardInput` the same way `process_window_event()` does.
+    /// The logical key, text and input kind are not affected.
+    ///
+    /// winit does not allow constructing a `KeyEvent` outside of winit, so this exists for benchmarks and tests.
+    #[doc(hidden)]
+    pub fn process_physical_key(
+        &mut self,
+        device_id: DeviceId,
+        physical_key: PhysicalKey,
+        state: ElementState,
+    ) {
+        let now = Instant::now();
+        if let Some(current) = &mut self.current {
+            current.handle_physical_key(physical_key, state, now);
+            if self.fixed_timestep.is_some() {
+                self.tick_pending
+                    .handle_physical_key(physical_key, state, now);
+            }
+            if let Some(device) = self.device_mut(device_id) {
+                device.handle_physical_key(physical_key, state, now);
+            }
+        }
+    }
+
+    fn set_input_kind(&mut self, kind: InputKind) {
+        if self.last_input_kind != kind {
+            self.last_input_kind = kind;
+            self.input_kind_changed = true;
+        }
+    }
+
+    /// Returns the per device input state, creating it if it doesnt exist yet.
+    /// Returns `None` if per device tracking is disabled.
+    fn device_mut(&mut self, device_id: DeviceId) -> Option<&mut CurrentInput> {
+        let devices = self.devices.as_mut()?;
+        let index = match devices.iter().position(|(id, _)| *id == device_id) {
+            Some(index) => index,
+            None => {
+                devices.push((device_id, CurrentInput::new()));
+                devices.len() - 1
+            }
+        };
+        Some(&mut devices[index].1)
+    }
+
+    fn device(&self, device_id: DeviceId) -> Option<&CurrentInput> {
+        self.devices
+            .as_ref()?
+            .iter()
+            .find(|(id, _)| *id == device_id)
+            .map(|(_, device)| device)
+    }
+
+    /// Enables or disables tracking input separately for each `DeviceId`, disabled by default.
+    ///
+    /// This allows e.g. local multiplayer with two keyboards or mice via `key_held_on()`, `mouse_diff_of()` etc.
+    /// The other accessor methods are not affected and continue to report input from all devices combined.
+    pub fn set_per_device_tracking(&mut self, enabled: bool) {
+        match (enabled, &self.devices) {
+            (true, None) => self.devices = Some(vec![]),
+            (false, Some(_)) => self.devices = None,
+            _ => {}
+        }
+    }
+
+    /// Call every time ApplicationHandler.suspended() is called.
+    /// Releases all held input, since the application will not receive the release events while suspended.
+    pub fn process_suspended(&mut self) {
+        self.suspended = true;
+        if let Some(current) = &mut self.current {
+            *current = CurrentInput::new();
         }
+        self.tick_pending = CurrentInput::new();
+        if let Some(devices) = &mut self.devices {
+            devices.clear();
+        }
+        self.raw = RawInput::new();
+    }
+
+    /// Call every time ApplicationHandler.resumed() is called.
+    pub fn process_resumed(&mut self) {
+        self.suspended = false;
+        self.resumed = true;
+    }
+
+    /// Call every time ApplicationHandler.memory_warning() is called.
+    pub fn process_memory_warning(&mut self) {
+        self.memory_warning = true;
     }
 
     // Call every time ApplicationHandler.about_to_wait() is called.
     // Update your application logic _after_ you call this function.
     pub fn end_step(&mut self) {
-        self.step_duration = self.step_start.map(|start| start.elapsed());
-        self.step_start = Some(Instant::now());
+        let now = Instant::now();
+        // `step()` clears `step_duration`, so the previous duration comes from the frame stats.
+        let prev_step_duration = self.frame_stats.durations.back().copied();
+        self.step_duration = self.step_start.map(|start| now.duration_since(start));
+        self.step_start = Some(now);
+        if let Some(duration) = self.step_duration {
+            self.frame_stats.record(duration);
+        }
+        self.step_jitter = match (prev_step_duration, self.step_duration) {
+            (Some(prev), Some(current)) => Some(current.abs_diff(prev)),
+            _ => None,
+        };
+        self.input_latency = self
+            .current
+            .as_ref()
+            .and_then(|current| current.first_transition_time())
+            .map(|time| now.duration_since(time));
+        if let (Some(timestep), Some(duration)) = (self.fixed_timestep, self.step_duration) {
+            self.tick_accumulator =
+                (self.tick_accumulator + duration).min(timestep * MAX_ACCUMULATED_TICKS);
+        }
+        self.update_kinetic_scroll();
+        self.mouse_diff_processed = self
+            .mouse_processing
+            .process(self.mouse_diff(), self.mouse_diff_processed);
+    }
+
+    fn update_kinetic_scroll(&mut self) {
+        let (Some(friction), Some(current)) = (self.kinetic_scroll_friction, &mut self.current)
+        else {
+            self.kinetic_scroll_velocity = (0.0, 0.0);
+            self.kinetic_scroll_active = false;
+            return;
+        };
+        let dt = match self.step_duration {
+            Some(duration) if !duration.is_zero() => duration.as_secs_f32(),
+            _ => return,
+        };
+
+        match current.scroll_phase {
+            Some(TouchPhase::Started) | Some(TouchPhase::Moved) => {
+                self.kinetic_scroll_velocity =
+                    (current.x_scroll_diff / dt, current.y_scroll_diff / dt);
+                self.kinetic_scroll_active = false;
+            }
+            Some(TouchPhase::Ended) => {
+                // A quick flick can start, move and end within a single step, so use its deltas when there are any.
+                if current.x_scroll_diff != 0.0 || current.y_scroll_diff != 0.0 {
+                    self.kinetic_scroll_velocity =
+                        (current.x_scroll_diff / dt, current.y_scroll_diff / dt);
+                }
+                self.kinetic_scroll_active = true;
+            }
+            Some(TouchPhase::Cancelled) => {
+                self.kinetic_scroll_velocity = (0.0, 0.0);
+                self.kinetic_scroll_active = false;
+            }
+            None if self.kinetic_scroll_active => {
+                let (x, y) = self.kinetic_scroll_velocity;
+                if x.abs().max(y.abs()) < KINETIC_SCROLL_MIN_VELOCITY {
+                    self.kinetic_scroll_velocity = (0.0, 0.0);
+                    self.kinetic_scroll_active = false;
+                    return;
+                }
+                current.x_scroll_diff += x * dt;
+                current.y_scroll_diff += y * dt;
+
+                let decay = (-friction * dt).exp();
+                self.kinetic_scroll_velocity = (x * decay, y * decay);
+            }
+            None => {}
+        }
+    }
+
+    /// Returns the input state that accessor methods should read from.
+    /// During a fixed timestep tick this is the input buffered for that tick.
+    fn input(&self) -> Option<&CurrentInput> {
+        if self.in_tick {
+            Some(&self.tick_current)
+        } else {
+            self.current.as_ref()
+        }
+    }
+
+    /// Enables fixed timestep mode, where input is buffered until it is observed by a simulation tick.
+    /// Pass `None` to disable, which is the default.
+    ///
+    /// Run your simulation like this, after calling `end_step()`:
+    /// ```
+    /// # let mut input = winit_input_helper::WinitInputHelper::new();
+    /// # input.set_fixed_timestep(Some(std::time::Duration::from_secs_f64(1.0 / 60.0)));
+    /// while input.tick_due() {
+    ///     input.begin_tick();
+    ///     // run one tick of your simulation, querying input as usual
+    ///     input.end_tick();
+    /// }
+    /// ```
+    /// Every press and release is then observed by exactly one tick, regardless of how many ticks run each step.
+    pub fn set_fixed_timestep(&mut self, timestep: Option<Duration>) {
+        self.fixed_timestep = timestep;
+        self.tick_accumulator = Duration::ZERO;
+        self.tick_pending = self.current.clone().unwrap_or_else(CurrentInput::new);
+        self.tick_pending.step();
+        self.in_tick = false;
+    }
+
+    /// Returns true if enough time has accumulated via `end_step()` to run another fixed timestep tick.
+    /// Otherwise returns false, including when fixed timestep mode is disabled.
+    ///
+    /// At most 10 ticks worth of time is accumulated, to avoid a spiral of ever longer steps when the simulation cant keep up.
+    pub fn tick_due(&self) -> bool {
+        match self.fixed_timestep {
+            Some(timestep) => self.tick_accumulator >= timestep,
+            None => false,
+        }
+    }
+
+    /// Starts a fixed timestep tick, consuming one timestep from the accumulated time.
+    /// Until `end_tick()` is called, accessor methods report the input that occured since the previous tick instead of during the last step.
+    ///
+    /// Does nothing if fixed timestep mode is disabled.
+    pub fn begin_tick(&mut self) {
+        let Some(timestep) = self.fixed_timestep else {
+            return;
+        };
+        self.tick_accumulator = self.tick_accumulator.saturating_sub(timestep);
+        self.tick_current.clone_from(&self.tick_pending);
+        self.tick_pending.step();
+        self.in_tick = true;
+    }
+
+    /// Ends a fixed timestep tick, accessor methods go back to reporting the input of the last step.
+    pub fn end_tick(&mut self) {
+        self.in_tick = false;
+    }
+
+    /// Returns how far between the previous and the next fixed timestep tick the current time is, from `0.0` to `1.0`.
+    /// Useful for interpolating rendering between simulation states.
+    ///
+    /// Returns `0.0` if fixed timestep mode is disabled.
+    pub fn tick_alpha(&self) -> f32 {
+        match self.fixed_timestep {
+            Some(timestep) if !timestep.is_zero() => {
+                (self.tick_accumulator.as_secs_f32() / timestep.as_secs_f32()).min(1.0)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Enables simulated momentum scrolling for platforms where the OS does not provide it.
+    ///
+    /// When a scroll gesture ends (`TouchPhase::Ended`) `scroll_diff()` will keep reporting scrolling
+    /// that decays over time, the velocity is multiplied by `e^(-friction * seconds)` each step.
+    /// Starting a new gesture stops the simulation.
+    ///
+    /// Only enable this on platforms that report `Ended` but provide no momentum of their own, e.g. Wayland.
+    /// On macOS the momentum from the OS ends with another `Ended`, which would start a second simulated momentum.
+    /// X11 and Windows only report `Moved`, so the simulation never starts there.
+    ///
+    /// Pass `None` to disable, which is the default.
+    pub fn set_kinetic_scrolling(&mut self, friction: Option<f32>) {
+        self.kinetic_scroll_friction = friction;
     }
 
     /// Returns true when the key with the specified keycode goes from "not pressed" to "pressed".
@@ -135,14 +589,13 @@ impl WinitInputHelper {
     ///
     /// This is suitable for game controls.
     pub fn key_pressed(&self, keycode: KeyCode) -> bool {
-        let key = PhysicalKey::Code(keycode);
-        if let Some(current) = &self.current {
-            let searched_action = ScanCodeAction::Pressed(key);
-            if current.scancode_actions.contains(&searched_action) {
-                return true;
-            }
+        if self.consumption.key(keycode, self.input_layer) {
+            return false;
+        }
+        match self.input() {
+            Some(current) => current.scancode_pressed.contains_code(keycode),
+            None => false,
         }
-        false
     }
 
     /// Returns true when the key with the specified keycode goes from "not pressed" to "pressed".
@@ -153,14 +606,13 @@ impl WinitInputHelper {
     /// Will repeat key presses while held down according to the OS's key repeat configuration
     /// This is suitable for UI.
     pub fn key_pressed_os(&self, keycode: KeyCode) -> bool {
-        let key = PhysicalKey::Code(keycode);
-        if let Some(current) = &self.current {
-            let searched_action = ScanCodeAction::PressedOs(key);
-            if current.scancode_actions.contains(&searched_action) {
-                return true;
-            }
+        if self.consumption.key(keycode, self.input_layer) {
+            return false;
+        }
+        match self.input() {
+            Some(current) => current.scancode_pressed_os.contains_code(keycode),
+            None => false,
         }
-        false
     }
 
     /// Returns true when the key with the specified KeyCode goes from "pressed" to "not pressed".
@@ -168,14 +620,13 @@ impl WinitInputHelper {
     ///
     /// Uses physical keys in the US layout, so for example the `W` key will be in the same physical key on both US and french keyboards.
     pub fn key_released(&self, keycode: KeyCode) -> bool {
-        let key = PhysicalKey::Code(keycode);
-        if let Some(current) = &self.current {
-            let searched_action = ScanCodeAction::Released(key);
-            if current.scancode_actions.contains(&searched_action) {
-                return true;
-            }
+        if self.consumption.key(keycode, self.input_layer) {
+            return false;
+        }
+        match self.input() {
+            Some(current) => current.scancode_released.contains_code(keycode),
+            None => false,
         }
-        false
     }
 
     /// Returns true when the key with the specified keycode remains "pressed".
@@ -183,11 +634,59 @@ impl WinitInputHelper {
     ///
     /// Uses physical keys in the US layout, so for example the `W` key will be in the same physical key on both US and french keyboards.
     pub fn key_held(&self, keycode: KeyCode) -> bool {
-        let key = PhysicalKey::Code(keycode);
-        if let Some(current) = &self.current {
-            return current.scancode_held.contains(&key);
+        if self.consumption.key(keycode, self.input_layer) {
+            return false;
+        }
+        match self.input() {
+            Some(current) => current.scancode_held.contains_code(keycode),
+            None => false,
+        }
+    }
+
+    /// Returns every key that went from "not pressed" to "pressed" during the last step, in the order they were pressed.
+    ///
+    /// Uses physical keys, see `key_pressed()`.
+    pub fn keys_pressed(&self) -> impl Iterator<Item = PhysicalKey> + '_ {
+        self.input()
+            .into_iter()
+            .flat_map(|current| current.scancode_pressed.iter())
+            .filter(|key| !self.physical_key_consumed(*key))
+    }
+
+    /// Returns every key that went from "pressed" to "not pressed" during the last step, in the order they were released.
+    ///
+    /// Uses physical keys, see `key_released()`.
+    pub fn keys_released(&self) -> impl Iterator<Item = PhysicalKey> + '_ {
+        self.input()
+            .into_iter()
+            .flat_map(|current| current.scancode_released.iter())
+            .filter(|key| !self.physical_key_consumed(*key))
+    }
+
+    /// Returns every key that is currently "pressed", in the order they were pressed.
+    /// Returns nothing when the window is not focused.
+    ///
+    /// Uses physical keys, see `key_held()`.
+    pub fn keys_held(&self) -> impl Iterator<Item = PhysicalKey> + '_ {
+        self.input()
+            .into_iter()
+            .flat_map(|current| current.scancode_held.iter())
+            .filter(|key| !self.physical_key_consumed(*key))
+    }
+
+    /// Returns true when any key goes from "not pressed" to "pressed".
+    /// Otherwise returns false.
+    ///
+    /// Only considers the keyboard, this is suitable for "press any key" screens.
+    pub fn any_key_pressed(&self) -> bool {
+        self.keys_pressed().next().is_some()
+    }
+
+    fn physical_key_consumed(&self, key: PhysicalKey) -> bool {
+        match key {
+            PhysicalKey::Code(keycode) => self.consumption.key(keycode, self.input_layer),
+            PhysicalKey::Unidentified(_) => self.consumption.keyboard(self.input_layer),
         }
-        false
     }
 
     /// Returns true while any shift key is held on the keyboard.
@@ -220,8 +719,11 @@ impl WinitInputHelper {
     /// Uses logical keypresses, so for example `W` is changed between a US and french keyboard.
     /// Will never repeat keypresses while held.
     pub fn key_pressed_logical(&self, check_key: Key<&str>) -> bool {
-        if let Some(current) = &self.current {
-            for action in &current.key_actions {
+        if self.consumption.keyboard(self.input_layer) {
+            return false;
+        }
+        if let Some(current) = self.input() {
+            for (action, _) in &current.key_actions {
                 if let KeyAction::Pressed(key) = action {
                     if key.as_ref() == check_key {
                         return true;
@@ -240,8 +742,11 @@ impl WinitInputHelper {
     /// Will repeat key presses while held down according to the OS's key repeat configuration
     /// This is suitable for UI.
     pub fn key_pressed_os_logical(&self, check_key: Key<&str>) -> bool {
-        if let Some(current) = &self.current {
-            for action in &current.key_actions {
+        if self.consumption.keyboard(self.input_layer) {
+            return false;
+        }
+        if let Some(current) = self.input() {
+            for (action, _) in &current.key_actions {
                 if let KeyAction::PressedOs(key_code) = action {
                     if key_code.as_ref() == check_key {
                         return true;
@@ -257,8 +762,11 @@ impl WinitInputHelper {
     ///
     /// Uses logical keypresses, so for example `W` is changed between a US and french keyboard.
     pub fn key_released_logical(&self, check_key: Key<&str>) -> bool {
-        if let Some(current) = &self.current {
-            for action in &current.key_actions {
+        if self.consumption.keyboard(self.input_layer) {
+            return false;
+        }
+        if let Some(current) = self.input() {
+            for (action, _) in &current.key_actions {
                 if let KeyAction::Released(key_code) = action {
                     if key_code.as_ref() == check_key {
                         return true;
@@ -274,17 +782,35 @@ impl WinitInputHelper {
     ///
     /// Uses logical keypresses, so for example `W` is changed between a US and french keyboard.
     pub fn key_held_logical(&self, check_key: Key<&str>) -> bool {
-        match &self.current {
+        if self.consumption.keyboard(self.input_layer) {
+            return false;
+        }
+        match self.input() {
             Some(current) => current.key_held.iter().any(|x| x.as_ref() == check_key),
             None => false,
         }
     }
 
+    /// Returns every logical key that is currently "pressed", in the order they were pressed.
+    /// Returns nothing when the window is not focused.
+    ///
+    /// Uses logical keys, see `key_held_logical()`.
+    pub fn logical_keys_held(&self) -> impl Iterator<Item = &Key> + '_ {
+        let consumed = self.consumption.keyboard(self.input_layer);
+        self.input()
+            .filter(|_| !consumed)
+            .into_iter()
+            .flat_map(|current| current.key_held.iter())
+    }
+
     /// Returns true when the specified mouse button goes from "not pressed" to "pressed".
     /// Otherwise returns false.
     pub fn mouse_pressed(&self, mouse_button: MouseButton) -> bool {
-        if let Some(current) = &self.current {
-            for action in &current.mouse_actions {
+        if self.consumption.mouse(mouse_button, self.input_layer) {
+            return false;
+        }
+        if let Some(current) = self.input() {
+            for (action, _) in &current.mouse_actions {
                 if let MouseAction::Pressed(key_code) = *action {
                     if key_code == mouse_button {
                         return true;
@@ -298,8 +824,11 @@ impl WinitInputHelper {
     /// Returns true when the specified mouse button goes from "pressed" to "not pressed".
     /// Otherwise returns false.
     pub fn mouse_released(&self, mouse_button: MouseButton) -> bool {
-        if let Some(current) = &self.current {
-            for action in &current.mouse_actions {
+        if self.consumption.mouse(mouse_button, self.input_layer) {
+            return false;
+        }
+        if let Some(current) = self.input() {
+            for (action, _) in &current.mouse_actions {
                 if let MouseAction::Released(key_code) = *action {
                     if key_code == mouse_button {
                         return true;
@@ -313,26 +842,104 @@ impl WinitInputHelper {
     /// Returns true while the specified mouse button remains "pressed".
     /// Otherwise returns false.
     pub fn mouse_held(&self, mouse_button: MouseButton) -> bool {
-        match &self.current {
-            Some(current) => current.mouse_held[mouse_button_to_int(&mouse_button)],
+        if self.consumption.mouse(mouse_button, self.input_layer) {
+            return false;
+        }
+        match self.input() {
+            Some(current) => current.mouse_held.contains(&mouse_button),
             None => false,
         }
     }
 
+    /// Returns every mouse button that went from "not pressed" to "pressed" during the last step, in the order they were pressed.
+    pub fn mouse_buttons_pressed(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        let actions = match self.input() {
+            Some(current) => current.mouse_actions.as_slice(),
+            None => &[],
+        };
+        actions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (action, _))| match action {
+                // A button pressed several times during the step is only returned once.
+                MouseAction::Pressed(button)
+                    if !actions[..i].iter().any(|(earlier, _)| earlier == action) =>
+                {
+                    Some(*button)
+                }
+                _ => None,
+            })
+            .filter(|button| !self.consumption.mouse(*button, self.input_layer))
+    }
+
+    /// Returns every mouse button that went from "pressed" to "not pressed" during the last step, in the order they were released.
+    pub fn mouse_buttons_released(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        let actions = match self.input() {
+            Some(current) => current.mouse_actions.as_slice(),
+            None => &[],
+        };
+        actions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (action, _))| match action {
+                MouseAction::Released(button)
+                    if !actions[..i].iter().any(|(earlier, _)| earlier == action) =>
+                {
+                    Some(*button)
+                }
+                _ => None,
+            })
+            .filter(|button| !self.consumption.mouse(*button, self.input_layer))
+    }
+
+    /// Returns every mouse button that is currently "pressed", in the order they were pressed.
+    /// Returns nothing when the window is not focused.
+    pub fn mouse_buttons_held(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        self.input()
+            .into_iter()
+            .flat_map(|current| current.mouse_held.iter().copied())
+            .filter(|button| !self.consumption.mouse(*button, self.input_layer))
+    }
+
     /// Returns `(0.0, 0.0)` when the window is not focused.
     /// Otherwise returns the amount scrolled by the mouse during the last step.
     /// Returns (horizontally, vertically)
     pub fn scroll_diff(&self) -> (f32, f32) {
-        match &self.current {
+        if self.consumption.scroll(self.input_layer) {
+            return (0.0, 0.0);
+        }
+        match self.input() {
             Some(current) => (current.x_scroll_diff, current.y_scroll_diff),
             None => (0.0, 0.0),
         }
     }
 
+    /// Returns the phase of the last scroll event received during the last step.
+    /// Returns `None` if no scroll events were received or the window is not focused.
+    ///
+    /// Touchpads on macOS and Wayland report `Started` and `Ended` at the start and end of a gesture.
+    /// On macOS the OS provided momentum scrolling is reported as another `Started` … `Ended` sequence,
+    /// so the gesture can not be told apart from its momentum.
+    /// X11, Windows and mouse wheels only ever report `Moved`.
+    pub fn scroll_phase(&self) -> Option<TouchPhase> {
+        match self.input() {
+            Some(current) => current.scroll_phase,
+            None => None,
+        }
+    }
+
+    /// Returns true if `scroll_diff()` during the last step came from simulated momentum scrolling.
+    /// Otherwise returns false.
+    ///
+    /// Only ever true when enabled via `set_kinetic_scrolling()`.
+    pub fn scroll_momentum(&self) -> bool {
+        self.kinetic_scroll_active && self.scroll_phase().is_none()
+    }
+
     /// Returns the cursor coordinates in pixels, when window is focused AND (cursor is on window OR any mouse button remains held while cursor moved off window)
     /// Otherwise returns `None`
     pub fn cursor(&self) -> Option<(f32, f32)> {
-        match &self.current {
+        match self.input() {
             Some(current) => current.cursor_point,
             None => None,
         }
@@ -341,7 +948,7 @@ impl WinitInputHelper {
     /// Returns the change in cursor coordinates that occured during the last step, when window is focused AND (cursor is on window OR any mouse button remains held while cursor moved off window)
     /// Otherwise returns `(0.0, 0.0)`.
     pub fn cursor_diff(&self) -> (f32, f32) {
-        if let Some(current_input) = &self.current {
+        if let Some(current_input) = self.input() {
             if let Some(cur) = current_input.cursor_point {
                 if let Some(prev) = current_input.cursor_point_prev {
                     return (cur.0 - prev.0, cur.1 - prev.1);
@@ -351,11 +958,62 @@ impl WinitInputHelper {
         (0.0, 0.0)
     }
 
+    /// Returns the most recent cursor positions along with the time they were received, oldest first.
+    /// Unlike `cursor_diff()` this includes every `CursorMoved` event, not just the last one of each step.
+    ///
+    /// The history is kept across steps and cleared when the window loses focus.
+    pub fn cursor_history(&self) -> impl Iterator<Item = &CursorSample> {
+        self.input()
+            .into_iter()
+            .flat_map(|current| current.cursor_history.iter())
+    }
+
+    /// Returns every cursor position received during the last step along with the time it was received, in the order they were received.
+    /// Returns an empty slice when the window is not focused.
+    ///
+    /// This is useful for drawing applications, where only using `cursor()` would turn strokes into polylines at low frame rates.
+    pub fn cursor_path(&self) -> &[CursorSample] {
+        match self.input() {
+            Some(current) => &current.cursor_path,
+            None => &[],
+        }
+    }
+
+    /// Returns the velocity of the cursor in pixels per second, estimated from the cursor movements of the last 100ms.
+    /// Returns `(0.0, 0.0)` if the cursor has not moved recently or the window is not focused.
+    ///
+    /// This is useful for flick gestures and throwing objects.
+    pub fn cursor_velocity(&self) -> (f32, f32) {
+        let Some(current) = self.input() else {
+            return (0.0, 0.0);
+        };
+        let Some(newest) = current.cursor_history.back() else {
+            return (0.0, 0.0);
+        };
+        if newest.time.elapsed() > CURSOR_VELOCITY_WINDOW {
+            return (0.0, 0.0);
+        }
+
+        let oldest = current
+            .cursor_history
+            .iter()
+            .find(|sample| newest.time.duration_since(sample.time) <= CURSOR_VELOCITY_WINDOW)
+            .unwrap_or(newest);
+        let seconds = newest.time.duration_since(oldest.time).as_secs_f32();
+        if seconds == 0.0 {
+            return (0.0, 0.0);
+        }
+        (
+            (newest.position.0 - oldest.position.0) / seconds,
+            (newest.position.1 - oldest.position.1) / seconds,
+        )
+    }
+
     /// Returns the change in mouse coordinates that occured during the last step.
     ///
     /// This is useful when implementing first person controls with a captured mouse.
     pub fn mouse_diff(&self) -> (f32, f32) {
-        if let Some(current_input) = &self.current {
+        if let Some(current_input) = self.input() {
             if let Some(diff) = current_input.mouse_diff {
                 return diff;
             }
@@ -363,60 +1021,1267 @@ impl WinitInputHelper {
         (0.0, 0.0)
     }
 
-    /// Returns the characters pressed during the last step.
-    /// The characters are in the order they were pressed.
-    pub fn text(&self) -> &[Key] {
-        match &self.current {
-            Some(current) => &current.text,
-            None => &[],
+    /// Returns the time the key with the specified keycode went from "not pressed" to "pressed" during the last step.
+    /// Otherwise returns `None`.
+    ///
+    /// The time is when `process_window_event()` received the event, which may be later than when the key was physically pressed.
+    pub fn key_pressed_at(&self, keycode: KeyCode) -> Option<Instant> {
+        if self.consumption.key(keycode, self.input_layer) {
+            return None;
+        }
+        let current = self.input()?;
+        if current.scancode_pressed.contains_code(keycode) {
+            current.scancode_pressed_at.get(keycode)
+        } else {
+            None
         }
     }
 
-    /// Returns the path to a file that has been drag-and-dropped onto the window.
-    pub fn dropped_file(&self) -> Option<PathBuf> {
-        self.dropped_file.clone()
+    /// Returns the time the key with the specified keycode went from "pressed" to "not pressed" during the last step.
+    /// Otherwise returns `None`.
+    ///
+    /// The time is when `process_window_event()` received the event, which may be later than when the key was physically released.
+    pub fn key_released_at(&self, keycode: KeyCode) -> Option<Instant> {
+        if self.consumption.key(keycode, self.input_layer) {
+            return None;
+        }
+        let current = self.input()?;
+        if current.scancode_released.contains_code(keycode) {
+            current.scancode_released_at.get(keycode)
+        } else {
+            None
+        }
     }
 
-    /// Returns the current window size if it was resized during the last step.
+    /// Returns the time the specified mouse button went from "not pressed" to "pressed" during the last step.
     /// Otherwise returns `None`.
-    pub fn window_resized(&self) -> Option<PhysicalSize<u32>> {
-        self.window_resized
+    pub fn mouse_pressed_at(&self, mouse_button: MouseButton) -> Option<Instant> {
+        if self.consumption.mouse(mouse_button, self.input_layer) {
+            return None;
+        }
+        self.input()?
+            .mouse_actions
+            .iter()
+            .find(|(action, _)| *action == MouseAction::Pressed(mouse_button))
+            .map(|(_, time)| *time)
     }
 
-    /// Returns `None` when no `WindowEvent::Resized` have been received yet.
-    /// After one has been received it returns the current resolution of the window.
-    pub fn resolution(&self) -> Option<(u32, u32)> {
-        self.window_size
+    /// Returns the time the specified mouse button went from "pressed" to "not pressed" during the last step.
+    /// Otherwise returns `None`.
+    pub fn mouse_released_at(&self, mouse_button: MouseButton) -> Option<Instant> {
+        if self.consumption.mouse(mouse_button, self.input_layer) {
+            return None;
+        }
+        self.input()?
+            .mouse_actions
+            .iter()
+            .find(|(action, _)| *action == MouseAction::Released(mouse_button))
+            .map(|(_, time)| *time)
     }
 
-    /// Returns the current scale factor if it was changed during the last step.
-    /// Otherwise returns `None`.
-    pub fn scale_factor_changed(&self) -> Option<f64> {
-        self.scale_factor_changed
+    /// Returns true when the key with the specified keycode goes from "not pressed" to "pressed" according to raw device events.
+    /// Otherwise returns false.
+    ///
+    /// Raw device events are received even when the window is not focused, making this suitable for global hotkeys.
+    /// Not all platforms deliver raw keyboard events.
+    pub fn raw_key_pressed(&self, keycode: KeyCode) -> bool {
+        self.raw.key_pressed.contains_code(keycode)
     }
 
-    /// Returns `None` when no `WindowEvent::ScaleFactorChanged` have been received yet.
-    /// After one has been received it returns the current scale_factor of the window.
-    pub fn scale_factor(&self) -> Option<f64> {
-        self.scale_factor
+    /// Returns true when the key with the specified keycode goes from "pressed" to "not pressed" according to raw device events.
+    /// Otherwise returns false.
+    ///
+    /// Raw device events are received even when the window is not focused.
+    pub fn raw_key_released(&self, keycode: KeyCode) -> bool {
+        self.raw.key_released.contains_code(keycode)
     }
 
-    /// Returns true if the window has been destroyed
+    /// Returns true while the key with the specified keycode remains "pressed" according to raw device events.
     /// Otherwise returns false.
-    /// Once this method has returned true once all following calls to this method will also return true.
-    pub fn destroyed(&self) -> bool {
-        self.destroyed
+    ///
+    /// Raw device events are received even when the window is not focused.
+    pub fn raw_key_held(&self, keycode: KeyCode) -> bool {
+        self.raw.key_held.contains_code(keycode)
     }
 
-    /// Returns true if the OS has requested the application to close during this step.
+    /// Returns true when the specified device button goes from "not pressed" to "pressed".
     /// Otherwise returns false.
-    pub fn close_requested(&self) -> bool {
-        self.close_requested
+    ///
+    /// Button ids are platform and device specific.
+    pub fn raw_button_pressed(&self, button: ButtonId) -> bool {
+        self.raw
+            .button_actions
+            .contains(&ButtonAction::Pressed(button))
     }
 
-    /// Returns the `std::time::Duration` elapsed since the last step.
-    /// Returns `None` if the step is still in progress.
-    pub fn delta_time(&self) -> Option<Duration> {
-        self.step_duration
+    /// Returns true when the specified device button goes from "pressed" to "not pressed".
+    /// Otherwise returns false.
+    ///
+    /// Button ids are platform and device specific.
+    pub fn raw_button_released(&self, button: ButtonId) -> bool {
+        self.raw
+            .button_actions
+            .contains(&ButtonAction::Released(button))
+    }
+
+    /// Returns true while the specified device button remains "pressed".
+    /// Otherwise returns false.
+    ///
+    /// Button ids are platform and device specific.
+    pub fn raw_button_held(&self, button: ButtonId) -> bool {
+        self.raw.button_held.contains(&button)
+    }
+
+    /// Returns the total motion reported on the specified axis during the last step.
+    /// Returns `0.0` if there was no motion.
+    ///
+    /// Axis ids and units are platform and device specific.
+    pub fn raw_motion(&self, axis: AxisId) -> f64 {
+        self.raw
+            .motion
+            .iter()
+            .find(|(a, _)| *a == axis)
+            .map(|(_, value)| *value)
+            .unwrap_or(0.0)
+    }
+
+    /// Returns the amount scrolled according to raw device events during the last step.
+    /// Returns (horizontally, vertically)
+    ///
+    /// Unlike `scroll_diff()` this is reported even when the window is not focused.
+    pub fn raw_scroll_diff(&self) -> (f32, f32) {
+        (self.raw.x_scroll_diff, self.raw.y_scroll_diff)
+    }
+
+    /// Returns the devices that were connected during the last step.
+    pub fn devices_added(&self) -> &[DeviceId] {
+        &self.raw.devices_added
+    }
+
+    /// Returns the devices that were disconnected during the last step.
+    pub fn devices_removed(&self) -> &[DeviceId] {
+        &self.raw.devices_removed
+    }
+
+    /// Returns every device that input has been received from since the window was focused.
+    /// Returns nothing if per device tracking is disabled, enable it with `set_per_device_tracking()`.
+    pub fn devices(&self) -> impl Iterator<Item = DeviceId> + '_ {
+        self.devices.iter().flatten().map(|(id, _)| *id)
+    }
+
+    /// Returns true when the key with the specified keycode goes from "not pressed" to "pressed" on the specified device.
+    /// Otherwise returns false.
+    ///
+    /// Always returns false if per device tracking is disabled.
+    pub fn key_pressed_on(&self, device_id: DeviceId, keycode: KeyCode) -> bool {
+        if self.consumption.key(keycode, self.input_layer) {
+            return false;
+        }
+        match self.device(device_id) {
+            Some(device) => device.scancode_pressed.contains_code(keycode),
+            None => false,
+        }
+    }
+
+    /// Returns true when the key with the specified keycode goes from "pressed" to "not pressed" on the specified device.
+    /// Otherwise returns false.
+    ///
+    /// Always returns false if per device tracking is disabled.
+    pub fn key_released_on(&self, device_id: DeviceId, keycode: KeyCode) -> bool {
+        if self.consumption.key(keycode, self.input_layer) {
+            return false;
+        }
+        match self.device(device_id) {
+            Some(device) => device.scancode_released.contains_code(keycode),
+            None => false,
+        }
+    }
+
+    /// Returns true while the key with the specified keycode remains "pressed" on the specified device.
+    /// Otherwise returns false.
+    ///
+    /// Always returns false if per device tracking is disabled.
+    pub fn key_held_on(&self, device_id: DeviceId, keycode: KeyCode) -> bool {
+        if self.consumption.key(keycode, self.input_layer) {
+            return false;
+        }
+        match self.device(device_id) {
+            Some(device) => device.scancode_held.contains_code(keycode),
+            None => false,
+        }
+    }
+
+    /// Returns true when the specified mouse button goes from "not pressed" to "pressed" on the specified device.
+    /// Otherwise returns false.
+    ///
+    /// Always returns false if per device tracking is disabled.
+    pub fn mouse_pressed_on(&self, device_id: DeviceId, mouse_button: MouseButton) -> bool {
+        if self.consumption.mouse(mouse_button, self.input_layer) {
+            return false;
+        }
+        match self.device(device_id) {
+            Some(device) => device
+                .mouse_actions
+                .iter()
+                .any(|(action, _)| *action == MouseAction::Pressed(mouse_button)),
+            None => false,
+        }
+    }
+
+    /// Returns true when the specified mouse button goes from "pressed" to "not pressed" on the specified device.
+    /// Otherwise returns false.
+    ///
+    /// Always returns false if per device tracking is disabled.
+    pub fn mouse_released_on(&self, device_id: DeviceId, mouse_button: MouseButton) -> bool {
+        if self.consumption.mouse(mouse_button, self.input_layer) {
+            return false;
+        }
+        match self.device(device_id) {
+            Some(device) => device
+                .mouse_actions
+                .iter()
+                .any(|(action, _)| *action == MouseAction::Released(mouse_button)),
+            None => false,
+        }
+    }
+
+    /// Returns true while the specified mouse button remains "pressed" on the specified device.
+    /// Otherwise returns false.
+    ///
+    /// Always returns false if per device tracking is disabled.
+    pub fn mouse_held_on(&self, device_id: DeviceId, mouse_button: MouseButton) -> bool {
+        if self.consumption.mouse(mouse_button, self.input_layer) {
+            return false;
+        }
+        match self.device(device_id) {
+            Some(device) => device.mouse_held.contains(&mouse_button),
+            None => false,
+        }
+    }
+
+    /// Returns the change in mouse coordinates reported by the specified device during the last step.
+    ///
+    /// Always returns `(0.0, 0.0)` if per device tracking is disabled.
+    pub fn mouse_diff_of(&self, device_id: DeviceId) -> (f32, f32) {
+        match self.device(device_id) {
+            Some(device) => device.mouse_diff.unwrap_or((0.0, 0.0)),
+            None => (0.0, 0.0),
+        }
+    }
+
+    /// Returns the amount scrolled by the specified device during the last step.
+    /// Returns (horizontally, vertically)
+    ///
+    /// Always returns `(0.0, 0.0)` if per device tracking is disabled.
+    pub fn scroll_diff_of(&self, device_id: DeviceId) -> (f32, f32) {
+        match self.device(device_id) {
+            Some(device) => (device.x_scroll_diff, device.y_scroll_diff),
+            None => (0.0, 0.0),
+        }
+    }
+
+    /// Returns the last cursor coordinates in pixels reported by the specified device.
+    ///
+    /// Always returns `None` if per device tracking is disabled.
+    pub fn cursor_of(&self, device_id: DeviceId) -> Option<(f32, f32)> {
+        self.device(device_id)?.cursor_point
+    }
+
+    /// Returns the change in mouse coordinates that occured during the last step after applying the configured `MouseProcessing`.
+    ///
+    /// Unlike `mouse_diff()` this is only updated by `end_step()`.
+    /// With the default `MouseProcessing` this is identical to `mouse_diff()`.
+    pub fn mouse_diff_processed(&self) -> (f32, f32) {
+        self.mouse_diff_processed
+    }
+
+    /// Configures the sensitivity, acceleration and smoothing used by `mouse_diff_processed()`.
+    pub fn set_mouse_processing(&mut self, mouse_processing: MouseProcessing) {
+        self.mouse_processing = mouse_processing;
+    }
+
+    /// Returns the state of the pen or stylus, when window is focused AND a pen has touched the window.
+    /// Otherwise returns `None`.
+    ///
+    /// Touches made by fingers are not reported.
+    /// Telling pens apart from fingers is only possible on some platforms, currently only iOS.
+    pub fn pen(&self) -> Option<Pen> {
+        match self.input() {
+            Some(current) => current.pen,
+            None => None,
+        }
+    }
+
+    /// Returns true when the pen goes from "not touching" to "touching" the surface.
+    /// Otherwise returns false.
+    pub fn pen_pressed(&self) -> bool {
+        match self.input() {
+            Some(current) => current.pen_pressed,
+            None => false,
+        }
+    }
+
+    /// Returns true when the pen goes from "touching" to "not touching" the surface.
+    /// Otherwise returns false.
+    pub fn pen_released(&self) -> bool {
+        match self.input() {
+            Some(current) => current.pen_released,
+            None => false,
+        }
+    }
+
+    /// Returns the kind of device the user last gave meaningful input with.
+    /// Returns `InputKind::KeyboardMouse` until any input is received.
+    ///
+    /// Pressing keys or buttons, scrolling, touching and moving the mouse further than the motion threshold in a single step all count as meaningful input.
+    /// Gamepad axes count once they move beyond the gamepad dead zone.
+    pub fn last_input_kind(&self) -> InputKind {
+        self.last_input_kind
+    }
+
+    /// Returns true if `last_input_kind()` changed during the last step.
+    /// Otherwise returns false.
+    pub fn input_kind_changed(&self) -> bool {
+        self.input_kind_changed
+    }
+
+    /// Sets how far in pixels the mouse or cursor has to move during a step before `last_input_kind()` changes to `InputKind::KeyboardMouse`, defaults to `4.0`.
+    /// This keeps jittery mice from changing the input kind.
+    pub fn set_input_kind_motion_threshold(&mut self, pixels: f32) {
+        self.input_kind_motion_threshold = pixels;
+    }
+
+    /// Sets the input layer that following queries and `consume_*` calls are made from, defaults to `0`.
+    ///
+    /// Inputs consumed from a layer are hidden from queries made from that layer and all lower layers, but not from higher layers.
+    /// So process your input layers from the highest to the lowest, e.g. a UI overlay on layer 1 before the game on layer 0.
+    /// The layer is kept across steps.
+    pub fn set_input_layer(&mut self, layer: u32) {
+        self.input_layer = layer;
+    }
+
+    /// Hides the key from all following physical key queries during this step, e.g. because a UI element handled it.
+    /// That is `key_pressed()` and the other `key_*` queries taking a `KeyCode`, including their `*_on` and `*_at` variants,
+    /// `keys_pressed()` and the other `keys_*` queries, `key_pressed_within()` and any action bound to the key.
+    ///
+    /// Logical key queries such as `key_pressed_logical()` can not be matched to a `KeyCode`, use `consume_all_keyboard()` to hide them.
+    ///
+    /// Only affects queries made from the current input layer or lower, see `set_input_layer()`.
+    pub fn consume_key(&mut self, keycode: KeyCode) {
+        self.consumption.consume_key(keycode, self.input_layer);
+    }
+
+    /// Hides the mouse button from all following `mouse_*` queries during this step, e.g. because a UI element was clicked.
+    /// This includes their `*_on` and `*_at` variants, `mouse_buttons_pressed()` and the other `mouse_buttons_*` queries and any action bound to the button.
+    ///
+    /// Only affects queries made from the current input layer or lower, see `set_input_layer()`.
+    pub fn consume_mouse(&mut self, mouse_button: MouseButton) {
+        self.consumption
+            .consume_mouse(mouse_button, self.input_layer);
+    }
+
+    /// Makes all following `scroll_diff()` calls return `(0.0, 0.0)` during this step.
+    ///
+    /// Only affects queries made from the current input layer or lower, see `set_input_layer()`.
+    pub fn consume_scroll(&mut self) {
+        self.consumption.consume_scroll(self.input_layer);
+    }
+
+    /// Hides every key from all following keyboard queries and `text()` during this step, e.g. because a text box is focused.
+    ///
+    /// Only affects queries made from the current input layer or lower, see `set_input_layer()`.
+    pub fn consume_all_keyboard(&mut self) {
+        self.consumption.consume_all_keyboard(self.input_layer);
+    }
+
+    /// Pushes an input context onto the top of the context stack.
+    ///
+    /// Takes effect at the next `step()` so that action queries are consistent for the entire step.
+    pub fn push_input_context(&mut self, context: InputContext) {
+        self.context_changes.push(ContextChange::Push(context));
+    }
+
+    /// Pops the input context on the top of the context stack.
+    ///
+    /// Takes effect at the next `step()` so that action queries are consistent for the entire step.
+    pub fn pop_input_context(&mut self) {
+        self.context_changes.push(ContextChange::Pop);
+    }
+
+    /// Removes every input context with the specified name from the context stack.
+    ///
+    /// Takes effect at the next `step()` so that action queries are consistent for the entire step.
+    pub fn remove_input_context(&mut self, name: &str) {
+        self.context_changes
+            .push(ContextChange::Remove(name.to_owned()));
+    }
+
+    /// Returns the names of the input contexts currently on the context stack, from the bottom to the top.
+    pub fn input_contexts(&self) -> impl Iterator<Item = &str> {
+        self.contexts.iter().map(|context| context.name())
+    }
+
+    /// Returns true when any input bound to the action goes from "not pressed" to "pressed".
+    /// Otherwise returns false.
+    ///
+    /// Bindings are resolved through the input context stack, inputs blocked by higher contexts do not trigger actions in lower contexts.
+    pub fn action_pressed(&self, action: &str) -> bool {
+        any_binding(&self.contexts, action, |binding| {
+            self.binding_pressed(binding)
+        })
+    }
+
+    /// Returns true when any input bound to the action goes from "pressed" to "not pressed".
+    /// Otherwise returns false.
+    ///
+    /// Bindings are resolved through the input context stack, inputs blocked by higher contexts do not trigger actions in lower contexts.
+    pub fn action_released(&self, action: &str) -> bool {
+        any_binding(&self.contexts, action, |binding| {
+            self.binding_released(binding)
+        })
+    }
+
+    /// Returns true while any input bound to the action remains "pressed".
+    /// Otherwise returns false.
+    ///
+    /// Bindings are resolved through the input context stack, inputs blocked by higher contexts do not trigger actions in lower contexts.
+    pub fn action_held(&self, action: &str) -> bool {
+        any_binding(&self.contexts, action, |binding| self.binding_held(binding))
+    }
+
+    /// Returns true if the key with the specified keycode went from "not pressed" to "pressed" within the specified duration.
+    /// Otherwise returns false.
+    ///
+    /// Useful for buffering game actions, e.g. allowing a jump that was pressed shortly before landing.
+    /// Presses are remembered for up to one second by default, see `set_press_history_duration()`.
+    /// Use `consume_buffered_key()` once the press has been acted on.
+    pub fn key_pressed_within(&self, keycode: KeyCode, duration: Duration) -> bool {
+        self.binding_pressed_within(Binding::Key(keycode), duration)
+    }
+
+    /// Returns true if any input bound to the action went from "not pressed" to "pressed" within the specified duration.
+    /// Otherwise returns false.
+    ///
+    /// Bindings are resolved through the current input context stack.
+    /// Use `consume_buffered_action()` once the press has been acted on.
+    pub fn action_pressed_within(&self, action: &str, duration: Duration) -> bool {
+        any_binding(&self.contexts, action, |binding| {
+            self.binding_pressed_within(binding, duration)
+        })
+    }
+
+    /// Forgets all remembered presses of the key, so that `key_pressed_within()` returns false until it is pressed again.
+    pub fn consume_buffered_key(&mut self, keycode: KeyCode) {
+        self.press_history
+            .retain(|(binding, _)| *binding != Binding::Key(keycode));
+    }
+
+    /// Forgets all remembered presses of the inputs bound to the action, so that `action_pressed_within()` returns false until it is pressed again.
+    pub fn consume_buffered_action(&mut self, action: &str) {
+        let mut bindings = vec![];
+        any_binding(&self.contexts, action, |binding| {
+            bindings.push(binding);
+            false
+        });
+        self.press_history
+            .retain(|(binding, _)| !bindings.contains(binding));
+    }
+
+    /// Sets how long presses are remembered for `key_pressed_within()` and `action_pressed_within()`, defaults to one second.
+    pub fn set_press_history_duration(&mut self, duration: Duration) {
+        self.press_history_duration = duration;
+    }
+
+    fn binding_pressed_within(&self, binding: Binding, duration: Duration) -> bool {
+        let binding_consumed = match binding {
+            Binding::Key(keycode) => self.consumption.key(keycode, self.input_layer),
+            Binding::Mouse(mouse_button) => self.consumption.mouse(mouse_button, self.input_layer),
+            #[cfg(feature = "gilrs")]
+            Binding::GamepadButton(_) => false,
+        };
+        !binding_consumed
+            && self
+                .press_history
+                .iter()
+                .any(|(b, time)| *b == binding && time.elapsed() <= duration)
+    }
+
+    fn binding_pressed(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(keycode) => self.key_pressed(keycode),
+            Binding::Mouse(mouse_button) => self.mouse_pressed(mouse_button),
+            #[cfg(feature = "gilrs")]
+            Binding::GamepadButton(button) => self
+                .gamepads()
+                .any(|gamepad| self.gamepad_pressed(gamepad, button)),
+        }
+    }
+
+    fn binding_released(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(keycode) => self.key_released(keycode),
+            Binding::Mouse(mouse_button) => self.mouse_released(mouse_button),
+            #[cfg(feature = "gilrs")]
+            Binding::GamepadButton(button) => self
+                .gamepads()
+                .any(|gamepad| self.gamepad_released(gamepad, button)),
+        }
+    }
+
+    fn binding_held(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(keycode) => self.key_held(keycode),
+            Binding::Mouse(mouse_button) => self.mouse_held(mouse_button),
+            #[cfg(feature = "gilrs")]
+            Binding::GamepadButton(button) => self
+                .gamepads()
+                .any(|gamepad| self.gamepad_held(gamepad, button)),
+        }
+    }
+
+    /// Returns the characters pressed during the last step.
+    /// The characters are in the order they were pressed.
+    pub fn text(&self) -> &[Key] {
+        if self.consumption.keyboard(self.input_layer) {
+            return &[];
+        }
+        match self.input() {
+            Some(current) => &current.text,
+            None => &[],
+        }
+    }
+
+    /// Returns an owned copy of the input state as seen by the query methods, e.g. `keys_held()` and `cursor()`.
+    ///
+    /// Snapshots can be compared, diffed with `InputSnapshot::diff()` and, with the `serde` feature, serialized.
+    /// This is useful for sending input over the network and for logging input while debugging.
+    pub fn snapshot(&self) -> InputSnapshot {
+        InputSnapshot {
+            focused: self.input().is_some(),
+            keys_held: self.keys_held().collect(),
+            keys_pressed: self.keys_pressed().collect(),
+            keys_released: self.keys_released().collect(),
+            mouse_buttons_held: self.mouse_buttons_held().collect(),
+            mouse_buttons_pressed: self.mouse_buttons_pressed().collect(),
+            mouse_buttons_released: self.mouse_buttons_released().collect(),
+            cursor: self.cursor(),
+            cursor_diff: self.cursor_diff(),
+            mouse_diff: self.mouse_diff(),
+            scroll_diff: self.scroll_diff(),
+            resolution: self.resolution(),
+            scale_factor: self.scale_factor(),
+            window_position: self.window_position(),
+            occluded: self.occluded(),
+            theme: self.theme(),
+            close_requested: self.close_requested(),
+            destroyed: self.destroyed(),
+        }
+    }
+
+    /// Returns the held keys, held mouse buttons, cursor and diffs as seen by the query methods, in a form that can be sent over the network.
+    ///
+    /// Call this once per tick and send it with `TickInput::encode()`, the receiver plays it back with `RemoteInput`.
+    pub fn tick_input(&self) -> TickInput {
+        let mut input = TickInput::new();
+        for key in self.keys_held() {
+            if let PhysicalKey::Code(keycode) = key {
+                input.set_key_held(keycode, true);
+            }
+        }
+        for mouse_button in self.mouse_buttons_held() {
+            input.set_mouse_held(mouse_button, true);
+        }
+        input.set_cursor(self.cursor());
+        input.set_mouse_diff(self.mouse_diff());
+        input.set_scroll_diff(self.scroll_diff());
+        input
+    }
+
+    /// Returns the paths to the files that have been drag-and-dropped onto the window during the last step.
+    pub fn dropped_files(&self) -> &[PathBuf] {
+        &self.dropped_files
+    }
+
+    /// Returns the paths to the files currently being dragged over the window.
+    ///
+    /// These are kept across steps until the files are dropped or the hover is cancelled.
+    pub fn hovered_files(&self) -> &[PathBuf] {
+        &self.hovered_files
+    }
+
+    /// Returns true if files were dragged off the window without being dropped during the last step.
+    /// Otherwise returns false.
+    pub fn file_hover_cancelled(&self) -> bool {
+        self.file_hover_cancelled
+    }
+
+    /// Returns the current window size if it was resized during the last step.
+    /// Otherwise returns `None`.
+    pub fn window_resized(&self) -> Option<PhysicalSize<u32>> {
+        self.window_resized
+    }
+
+    /// Returns `None` when no `WindowEvent::Resized` have been received yet.
+    /// After one has been received it returns the current resolution of the window.
+    pub fn resolution(&self) -> Option<(u32, u32)> {
+        self.window_size
+    }
+
+    /// Returns the current scale factor if it was changed during the last step.
+    /// Otherwise returns `None`.
+    pub fn scale_factor_changed(&self) -> Option<f64> {
+        self.scale_factor_changed
+    }
+
+    /// Returns `None` when no `WindowEvent::ScaleFactorChanged` have been received yet.
+    /// After one has been received it returns the current scale_factor of the window.
+    pub fn scale_factor(&self) -> Option<f64> {
+        self.scale_factor
+    }
+
+    /// Returns the current window position if it was moved during the last step.
+    /// Otherwise returns `None`.
+    pub fn window_moved(&self) -> Option<PhysicalPosition<i32>> {
+        self.window_moved
+    }
+
+    /// Returns `None` when no `WindowEvent::Moved` have been received yet.
+    /// After one has been received it returns the current position of the window.
+    pub fn window_position(&self) -> Option<(i32, i32)> {
+        self.window_position
+    }
+
+    /// Returns true while the window is fully hidden from view, e.g. minimized or covered by another window.
+    /// Otherwise returns false.
+    ///
+    /// You may want to stop rendering while the window is occluded.
+    /// Not all platforms report occlusion.
+    pub fn occluded(&self) -> bool {
+        self.occluded
+    }
+
+    /// Returns the current theme if it was changed during the last step.
+    /// Otherwise returns `None`.
+    pub fn theme_changed(&self) -> Option<Theme> {
+        self.theme_changed
+    }
+
+    /// Returns `None` when no `WindowEvent::ThemeChanged` have been received yet.
+    /// After one has been received it returns the current theme of the window.
+    pub fn theme(&self) -> Option<Theme> {
+        self.theme
+    }
+
+    /// Returns the activation tokens received during the last step, in response to `Window::request_activation_token()`.
+    /// The serial identifies which request the token belongs to.
+    pub fn activation_tokens(&self) -> &[(AsyncRequestSerial, ActivationToken)] {
+        &self.activation_tokens
+    }
+
+    /// Returns true if the window has been destroyed
+    /// Otherwise returns false.
+    /// Once this method has returned true once all following calls to this method will also return true.
+    pub fn destroyed(&self) -> bool {
+        self.destroyed
+    }
+
+    /// Returns true if the OS has requested the application to close during this step.
+    /// Otherwise returns false.
+    pub fn close_requested(&self) -> bool {
+        self.close_requested
+    }
+
+    /// Returns true while the application is suspended, between calls to `process_suspended()` and `process_resumed()`.
+    /// Otherwise returns false.
+    pub fn suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Returns true if the application was resumed during this step, this includes when the application first starts.
+    /// Otherwise returns false.
+    pub fn resumed_this_step(&self) -> bool {
+        self.resumed
+    }
+
+    /// Returns true if the OS warned that the application is using too much memory during this step.
+    /// Otherwise returns false.
+    ///
+    /// You should free any memory you can, e.g. caches.
+    pub fn memory_warning(&self) -> bool {
+        self.memory_warning
+    }
+
+    /// Returns the time between the earliest key or mouse button event received during the last step and the call to `end_step()`.
+    /// Returns `None` if no such events were received.
+    ///
+    /// This measures how long input waits in the event queue before your application logic sees it, useful for diagnosing input lag.
+    pub fn input_latency(&self) -> Option<Duration> {
+        self.input_latency
+    }
+
+    /// Returns the difference between the durations of the last two steps.
+    /// Returns `None` until two steps have completed.
+    pub fn step_jitter(&self) -> Option<Duration> {
+        self.step_jitter
+    }
+
+    /// Returns the average duration of the recent steps.
+    /// Returns `None` until a step has completed.
+    ///
+    /// By default the last 120 steps are used, see `set_frame_stats_window()`.
+    pub fn average_delta_time(&self) -> Option<Duration> {
+        self.frame_stats.average()
+    }
+
+    /// Returns the number of steps per second averaged over the recent steps.
+    /// Returns `None` until a step has completed.
+    pub fn fps(&self) -> Option<f32> {
+        self.average_delta_time()
+            .filter(|average| !average.is_zero())
+            .map(|average| 1.0 / average.as_secs_f32())
+    }
+
+    /// Returns the duration of the shortest of the recent steps.
+    /// Returns `None` until a step has completed.
+    pub fn min_delta_time(&self) -> Option<Duration> {
+        self.frame_stats.min()
+    }
+
+    /// Returns the duration of the longest of the recent steps.
+    /// Returns `None` until a step has completed.
+    pub fn max_delta_time(&self) -> Option<Duration> {
+        self.frame_stats.max()
+    }
+
+    /// Returns the step duration that the specified percentage of the recent steps took at most, e.g. `99.0` for the 99th percentile.
+    /// Returns `None` until a step has completed.
+    pub fn delta_time_percentile(&self, percentile: f32) -> Option<Duration> {
+        self.frame_stats.percentile(percentile)
+    }
+
+    /// Sets how many of the most recent steps are used for the frame statistics, defaults to 120.
+    pub fn set_frame_stats_window(&mut self, steps: usize) {
+        self.frame_stats.set_window(steps);
+    }
+
+    /// Returns the time elapsed since the `WinitInputHelper` was created.
+    pub fn elapsed_time(&self) -> Duration {
+        self.frame_stats.created.elapsed()
+    }
+
+    /// Returns the number of steps completed since the `WinitInputHelper` was created.
+    pub fn step_count(&self) -> u64 {
+        self.frame_stats.step_count
+    }
+
+    /// Returns the `std::time::Duration` elapsed since the last step.
+    /// Returns `None` if the step is still in progress.
+    pub fn delta_time(&self) -> Option<Duration> {
+        self.step_duration
+    }
+}
+
+#[cfg(feature = "gilrs")]
+impl WinitInputHelper {
+    /// Replaces the `Gilrs` instance used to poll gamepads.
+    ///
+    /// Useful when you need to configure gilrs via `gilrs::GilrsBuilder`, e.g. to add custom mappings or to disable the platform backend.
+    /// Also needed to poll gamepads from a cloned `WinitInputHelper`, as clones do not share the `Gilrs` instance.
+    pub fn set_gilrs(&mut self, gilrs: Gilrs) {
+        let dead_zone = self.gamepads.dead_zone;
+        self.gamepads = GamepadInput::with_gilrs(Some(gilrs));
+        self.gamepads.dead_zone = dead_zone;
+    }
+
+    /// Sets the dead zone applied to gamepad axes and sticks, defaults to `0.1`.
+    ///
+    /// Values with a magnitude below the dead zone are reported as `0.0`, values above it are rescaled to still cover the full range.
+    pub fn set_gamepad_dead_zone(&mut self, dead_zone: f32) {
+        self.gamepads.dead_zone = dead_zone.clamp(0.0, 0.99);
+    }
+
+    /// Returns every currently connected gamepad.
+    pub fn gamepads(&self) -> impl Iterator<Item = GamepadId> + '_ {
+        self.gamepads.gamepads.iter().map(|gamepad| gamepad.id)
+    }
+
+    /// Returns true if the specified gamepad is connected and supports rumble effects.
+    /// Otherwise returns false.
+    pub fn gamepad_supports_rumble(&self, gamepad: GamepadId) -> bool {
+        self.gamepads.supports_rumble(gamepad)
+    }
+
+    /// Starts rumbling the specified gamepad, replacing any rumble that is still playing on it.
+    ///
+    /// Returns an error if the gamepad is disconnected or does not support rumble, see `gamepad_supports_rumble()`.
+    pub fn gamepad_rumble(
+        &mut self,
+        gamepad: GamepadId,
+        rumble: Rumble,
+    ) -> Result<(), gilrs::ff::Error> {
+        self.gamepads.rumble(gamepad, rumble)
+    }
+
+    /// Stops any rumble playing on the specified gamepad.
+    pub fn gamepad_stop_rumble(&mut self, gamepad: GamepadId) {
+        self.gamepads.stop_rumble(gamepad);
+    }
+
+    /// Returns the gamepads that were connected during the last step.
+    pub fn gamepads_connected(&self) -> &[GamepadId] {
+        &self.gamepads.connected
+    }
+
+    /// Returns the gamepads that were disconnected during the last step.
+    pub fn gamepads_disconnected(&self) -> &[GamepadId] {
+        &self.gamepads.disconnected
+    }
+
+    /// Returns true when the specified button on the specified gamepad goes from "not pressed" to "pressed".
+    /// Otherwise returns false.
+    pub fn gamepad_pressed(&self, gamepad: GamepadId, button: Button) -> bool {
+        match self.gamepads.gamepad(gamepad) {
+            Some(gamepad) => gamepad
+                .button_actions
+                .contains(&GamepadButtonAction::Pressed(button)),
+            None => false,
+        }
+    }
+
+    /// Returns true when the specified button on the specified gamepad goes from "pressed" to "not pressed".
+    /// Otherwise returns false.
+    pub fn gamepad_released(&self, gamepad: GamepadId, button: Button) -> bool {
+        match self.gamepads.gamepad(gamepad) {
+            Some(gamepad) => gamepad
+                .button_actions
+                .contains(&GamepadButtonAction::Released(button)),
+            None => false,
+        }
+    }
+
+    /// Returns true while the specified button on the specified gamepad remains "pressed".
+    /// Otherwise returns false.
+    pub fn gamepad_held(&self, gamepad: GamepadId, button: Button) -> bool {
+        match self.gamepads.gamepad(gamepad) {
+            Some(gamepad) => gamepad.button_held.contains(&button),
+            None => false,
+        }
+    }
+
+    /// Returns the value of the specified axis on the specified gamepad, from `-1.0` to `1.0`.
+    /// Returns `0.0` if the gamepad is not connected or the value is within the dead zone.
+    pub fn gamepad_axis(&self, gamepad: GamepadId, axis: Axis) -> f32 {
+        self.gamepads.axis(gamepad, axis)
+    }
+
+    /// Returns the position of the left stick on the specified gamepad, each axis from `-1.0` to `1.0`.
+    /// Returns `(0.0, 0.0)` if the gamepad is not connected or the stick is within the dead zone.
+    ///
+    /// Unlike `gamepad_axis()` the dead zone is applied to the stick as a whole.
+    pub fn gamepad_left_stick(&self, gamepad: GamepadId) -> (f32, f32) {
+        self.gamepads
+            .stick(gamepad, Axis::LeftStickX, Axis::LeftStickY)
+    }
+
+    /// Returns the position of the right stick on the specified gamepad, each axis from `-1.0` to `1.0`.
+    /// Returns `(0.0, 0.0)` if the gamepad is not connected or the stick is within the dead zone.
+    ///
+    /// Unlike `gamepad_axis()` the dead zone is applied to the stick as a whole.
+    pub fn gamepad_right_stick(&self, gamepad: GamepadId) -> (f32, f32) {
+        self.gamepads
+            .stick(gamepad, Axis::RightStickX, Axis::RightStickY)
+    }
+}
+
+impl InputQuery for WinitInputHelper {
+    fn key_pressed(&self, keycode: KeyCode) -> bool {
+        WinitInputHelper::key_pressed(self, keycode)
+    }
+
+    fn key_released(&self, keycode: KeyCode) -> bool {
+        WinitInputHelper::key_released(self, keycode)
+    }
+
+    fn key_held(&self, keycode: KeyCode) -> bool {
+        WinitInputHelper::key_held(self, keycode)
+    }
+
+    fn mouse_pressed(&self, mouse_button: MouseButton) -> bool {
+        WinitInputHelper::mouse_pressed(self, mouse_button)
+    }
+
+    fn mouse_released(&self, mouse_button: MouseButton) -> bool {
+        WinitInputHelper::mouse_released(self, mouse_button)
+    }
+
+    fn mouse_held(&self, mouse_button: MouseButton) -> bool {
+        WinitInputHelper::mouse_held(self, mouse_button)
+    }
+
+    fn cursor(&self) -> Option<(f32, f32)> {
+        WinitInputHelper::cursor(self)
+    }
+
+    fn cursor_diff(&self) -> (f32, f32) {
+        WinitInputHelper::cursor_diff(self)
+    }
+
+    fn mouse_diff(&self) -> (f32, f32) {
+        WinitInputHelper::mouse_diff(self)
+    }
+
+    fn scroll_diff(&self) -> (f32, f32) {
+        WinitInputHelper::scroll_diff(self)
+    }
+}
+
+/// Returns the device that caused a window event, for the events that `CurrentInput` handles.
+fn window_event_device_id(event: &WindowEvent) -> Option<DeviceId> {
+    match event {
+        WindowEvent::KeyboardInput { device_id, .. }
+        | WindowEvent::CursorMoved { device_id, .. }
+        | WindowEvent::MouseWheel { device_id, .. }
+        | WindowEvent::MouseInput { device_id, .. } => Some(*device_id),
+        WindowEvent::Touch(touch) => Some(touch.device_id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::event::MouseScrollDelta;
+
+    fn mouse_input(input: &mut WinitInputHelper, button: MouseButton, state: ElementState) {
+        input.process_window_event(&WindowEvent::MouseInput {
+            device_id: DeviceId::dummy(),
+            state,
+            button,
+        });
+    }
+
+    #[test]
+    fn exotic_mouse_buttons_are_held_and_released() {
+        let mut input = WinitInputHelper::new();
+        let buttons = [MouseButton::Other(250), MouseButton::Other(u16::MAX)];
+
+        input.step();
+        for button in buttons {
+            mouse_input(&mut input, button, ElementState::Pressed);
+        }
+        input.end_step();
+        for button in buttons {
+            assert!(input.mouse_pressed(button));
+            assert!(input.mouse_held(button));
+        }
+        assert_eq!(input.mouse_buttons_held().collect::<Vec<_>>(), buttons);
+        assert_eq!(input.mouse_buttons_pressed().collect::<Vec<_>>(), buttons);
+
+        input.step();
+        input.end_step();
+        assert!(!input.mouse_pressed(MouseButton::Other(250)));
+        assert!(input.mouse_held(MouseButton::Other(u16::MAX)));
+        assert_eq!(input.mouse_buttons_held().collect::<Vec<_>>(), buttons);
+
+        input.step();
+        mouse_input(
+            &mut input,
+            MouseButton::Other(u16::MAX),
+            ElementState::Released,
+        );
+        input.end_step();
+        assert!(input.mouse_released(MouseButton::Other(u16::MAX)));
+        assert!(!input.mouse_held(MouseButton::Other(u16::MAX)));
+        assert!(input.mouse_held(MouseButton::Other(250)));
+        assert_eq!(
+            input.mouse_buttons_held().collect::<Vec<_>>(),
+            [MouseButton::Other(250)]
+        );
+
+        input.step();
+        mouse_input(&mut input, MouseButton::Other(250), ElementState::Released);
+        input.end_step();
+        assert!(!input.mouse_held(MouseButton::Other(250)));
+        assert_eq!(input.mouse_buttons_held().count(), 0);
+    }
+
+    #[test]
+    fn exotic_mouse_buttons_do_not_alias_standard_buttons() {
+        let mut input = WinitInputHelper::new();
+        input.step();
+        mouse_input(&mut input, MouseButton::Other(0), ElementState::Pressed);
+        input.end_step();
+        assert!(input.mouse_held(MouseButton::Other(0)));
+        for button in [
+            MouseButton::Left,
+            MouseButton::Right,
+            MouseButton::Middle,
+            MouseButton::Back,
+            MouseButton::Forward,
+            MouseButton::Other(1),
+        ] {
+            assert!(!input.mouse_held(button));
+        }
+    }
+
+    #[test]
+    fn exotic_mouse_buttons_are_tracked_per_device() {
+        let mut input = WinitInputHelper::new();
+        input.set_per_device_tracking(true);
+        input.step();
+        mouse_input(
+            &mut input,
+            MouseButton::Other(u16::MAX),
+            ElementState::Pressed,
+        );
+        input.end_step();
+        assert!(input.mouse_held_on(DeviceId::dummy(), MouseButton::Other(u16::MAX)));
+        assert!(!input.mouse_held_on(DeviceId::dummy(), MouseButton::Other(250)));
+    }
+
+    #[test]
+    fn repeated_presses_hold_a_button_once() {
+        let mut input = WinitInputHelper::new();
+        input.step();
+        mouse_input(
+            &mut input,
+            MouseButton::Other(u16::MAX),
+            ElementState::Pressed,
+        );
+        mouse_input(
+            &mut input,
+            MouseButton::Other(u16::MAX),
+            ElementState::Pressed,
+        );
+        input.end_step();
+        assert_eq!(input.mouse_buttons_held().count(), 1);
+        assert_eq!(input.mouse_buttons_pressed().count(), 1);
+    }
+
+    fn run_steps(input: &mut WinitInputHelper, durations: &[Duration]) {
+        for duration in durations {
+            input.step();
+            std::thread::sleep(*duration);
+            input.end_step();
+        }
+    }
+
+    #[test]
+    fn step_jitter_needs_two_completed_steps() {
+        let mut input = WinitInputHelper::new();
+        run_steps(&mut input, &[Duration::from_millis(1)]);
+        assert!(input.delta_time().is_some());
+        assert_eq!(input.step_jitter(), None);
+
+        for _ in 0..3 {
+            run_steps(&mut input, &[Duration::from_millis(1)]);
+            assert!(input.step_jitter().is_some());
+        }
+    }
+
+    #[test]
+    fn step_jitter_is_difference_of_last_two_steps() {
+        let mut input = WinitInputHelper::new();
+        run_steps(
+            &mut input,
+            &[Duration::from_millis(1), Duration::from_millis(30)],
+        );
+        let durations = &input.frame_stats.durations;
+        let expected = durations[1].abs_diff(durations[0]);
+        assert_eq!(input.step_jitter(), Some(expected));
+        assert!(expected >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn step_jitter_works_with_a_single_step_window() {
+        let mut input = WinitInputHelper::new();
+        input.set_frame_stats_window(1);
+        run_steps(&mut input, &[Duration::ZERO; 2]);
+        assert!(input.step_jitter().is_some());
+    }
+
+    #[test]
+    fn delta_time_is_cleared_while_a_step_is_in_progress() {
+        let mut input = WinitInputHelper::new();
+        run_steps(&mut input, &[Duration::from_millis(1)]);
+        assert!(input.delta_time().unwrap() >= Duration::from_millis(1));
+        input.step();
+        assert_eq!(input.delta_time(), None);
+    }
+
+    #[test]
+    fn frame_stats_cover_the_recent_steps() {
+        let mut input = WinitInputHelper::new();
+        assert_eq!(input.average_delta_time(), None);
+        assert_eq!(input.fps(), None);
+        assert_eq!(input.step_count(), 0);
+
+        run_steps(
+            &mut input,
+            &[
+                Duration::from_millis(1),
+                Duration::from_millis(5),
+                Duration::from_millis(10),
+            ],
+        );
+        assert_eq!(input.step_count(), 3);
+        let min = input.min_delta_time().unwrap();
+        let max = input.max_delta_time().unwrap();
+        let average = input.average_delta_time().unwrap();
+        assert!(min <= average && average <= max);
+        assert!(max >= Duration::from_millis(10));
+        assert_eq!(input.delta_time_percentile(0.0), Some(min));
+        assert_eq!(input.delta_time_percentile(100.0), Some(max));
+        assert!(input.fps().unwrap() > 0.0);
+
+        input.set_frame_stats_window(1);
+        assert_eq!(input.min_delta_time(), Some(max));
+        assert_eq!(input.average_delta_time(), Some(max));
+    }
+
+    #[test]
+    fn input_latency_measures_time_since_first_event() {
+        let mut input = WinitInputHelper::new();
+        input.step();
+        input.end_step();
+        assert_eq!(input.input_latency(), None);
+
+        input.step();
+        mouse_input(&mut input, MouseButton::Left, ElementState::Pressed);
+        std::thread::sleep(Duration::from_millis(2));
+        input.end_step();
+        assert!(input.input_latency().unwrap() >= Duration::from_millis(2));
+    }
+
+    fn scroll(input: &mut WinitInputHelper, y: f64, phase: TouchPhase) {
+        input.process_window_event(&WindowEvent::MouseWheel {
+            device_id: DeviceId::dummy(),
+            delta: MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, y)),
+            phase,
+        });
+    }
+
+    #[test]
+    fn flick_within_a_single_step_has_momentum() {
+        let mut input = WinitInputHelper::new();
+        input.set_kinetic_scrolling(Some(4.0));
+        run_steps(&mut input, &[Duration::ZERO]);
+
+        input.step();
+        scroll(&mut input, 0.0, TouchPhase::Started);
+        scroll(&mut input, 50.0, TouchPhase::Moved);
+        scroll(&mut input, 0.0, TouchPhase::Ended);
+        std::thread::sleep(Duration::from_millis(5));
+        input.end_step();
+        assert!(!input.scroll_momentum());
+
+        run_steps(&mut input, &[Duration::from_millis(5)]);
+        assert!(input.scroll_momentum());
+        assert!(input.scroll_diff().1 > 0.0);
+    }
+
+    #[test]
+    fn ended_without_deltas_keeps_velocity_of_previous_step() {
+        let mut input = WinitInputHelper::new();
+        input.set_kinetic_scrolling(Some(4.0));
+        run_steps(&mut input, &[Duration::ZERO]);
+
+        input.step();
+        scroll(&mut input, -50.0, TouchPhase::Moved);
+        std::thread::sleep(Duration::from_millis(5));
+        input.end_step();
+
+        input.step();
+        scroll(&mut input, 0.0, TouchPhase::Ended);
+        input.end_step();
+
+        run_steps(&mut input, &[Duration::from_millis(5)]);
+        assert!(input.scroll_momentum());
+        assert!(input.scroll_diff().1 < 0.0);
+    }
+
+    #[test]
+    fn cancelled_scroll_has_no_momentum() {
+        let mut input = WinitInputHelper::new();
+        input.set_kinetic_scrolling(Some(4.0));
+        run_steps(&mut input, &[Duration::ZERO]);
+
+        input.step();
+        scroll(&mut input, 50.0, TouchPhase::Moved);
+        scroll(&mut input, 0.0, TouchPhase::Cancelled);
+        input.end_step();
+
+        run_steps(&mut input, &[Duration::from_millis(5)]);
+        assert!(!input.scroll_momentum());
+        assert_eq!(input.scroll_diff(), (0.0, 0.0));
+    }
+
+    fn physical_key(input: &mut WinitInputHelper, keycode: KeyCode, state: ElementState) {
+        input.process_physical_key(DeviceId::dummy(), PhysicalKey::Code(keycode), state);
+    }
+
+    #[test]
+    fn consumed_key_is_hidden_from_physical_key_queries() {
+        let mut input = WinitInputHelper::new();
+        input.set_per_device_tracking(true);
+        input.step();
+        physical_key(&mut input, KeyCode::KeyW, ElementState::Pressed);
+        physical_key(&mut input, KeyCode::KeyS, ElementState::Pressed);
+        input.end_step();
+        assert!(input.key_pressed_on(DeviceId::dummy(), KeyCode::KeyW));
+
+        input.consume_key(KeyCode::KeyW);
+        assert!(!input.key_pressed(KeyCode::KeyW));
+        assert!(!input.key_held(KeyCode::KeyW));
+        assert!(input.key_pressed_at(KeyCode::KeyW).is_none());
+        assert!(!input.key_pressed_on(DeviceId::dummy(), KeyCode::KeyW));
+        assert!(!input.key_held_on(DeviceId::dummy(), KeyCode::KeyW));
+        assert_eq!(
+            input.keys_held().collect::<Vec<_>>(),
+            [PhysicalKey::Code(KeyCode::KeyS)]
+        );
+        assert!(input.key_held_on(DeviceId::dummy(), KeyCode::KeyS));
+
+        input.step();
+        physical_key(&mut input, KeyCode::KeyW, ElementState::Released);
+        input.end_step();
+        assert!(input.key_released_on(DeviceId::dummy(), KeyCode::KeyW));
+        input.consume_all_keyboard();
+        assert!(!input.key_released_on(DeviceId::dummy(), KeyCode::KeyW));
+        assert!(!input.key_held_on(DeviceId::dummy(), KeyCode::KeyS));
+    }
+
+    #[test]
+    fn consumed_key_is_visible_to_higher_layers() {
+        let mut input = WinitInputHelper::new();
+        input.set_per_device_tracking(true);
+        input.step();
+        physical_key(&mut input, KeyCode::KeyW, ElementState::Pressed);
+        input.end_step();
+
+        input.consume_key(KeyCode::KeyW);
+        input.set_input_layer(1);
+        assert!(input.key_held(KeyCode::KeyW));
+        assert!(input.key_held_on(DeviceId::dummy(), KeyCode::KeyW));
+    }
+
+    #[test]
+    fn consumed_mouse_button_is_hidden_from_per_device_queries() {
+        let mut input = WinitInputHelper::new();
+        input.set_per_device_tracking(true);
+        input.step();
+        mouse_input(&mut input, MouseButton::Left, ElementState::Pressed);
+        input.end_step();
+
+        input.consume_mouse(MouseButton::Left);
+        assert!(!input.mouse_pressed_on(DeviceId::dummy(), MouseButton::Left));
+        assert!(!input.mouse_held_on(DeviceId::dummy(), MouseButton::Left));
+
+        input.step();
+        mouse_input(&mut input, MouseButton::Left, ElementState::Released);
+        input.end_step();
+        assert!(input.mouse_released_on(DeviceId::dummy(), MouseButton::Left));
+        input.consume_mouse(MouseButton::Left);
+        assert!(!input.mouse_released_on(DeviceId::dummy(), MouseButton::Left));
     }
 }